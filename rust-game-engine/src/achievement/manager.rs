@@ -1,5 +1,5 @@
 use wasm_bindgen::prelude::*;
-use serde::{Deserialize, Serialize};
+use crate::error::{from_js, to_js};
 use crate::achievement::{Achievement, GameStats, AchievementCategory, AchievementCondition};
 
 #[wasm_bindgen]
@@ -9,6 +9,12 @@ pub struct AchievementManager {
     unlocked_count: u32,
 }
 
+impl Default for AchievementManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[wasm_bindgen]
 impl AchievementManager {
     #[wasm_bindgen(constructor)]
@@ -27,8 +33,8 @@ impl AchievementManager {
         }
     }
 
-    pub fn get_achievements(&self) -> JsValue {
-        serde_wasm_bindgen::to_value(&self.achievements).unwrap()
+    pub fn get_achievements(&self) -> Result<JsValue, JsValue> {
+        to_js(&self.achievements)
     }
 
     pub fn get_total_points(&self) -> u32 {
@@ -39,13 +45,13 @@ impl AchievementManager {
         self.unlocked_count
     }
 
-    pub fn check_achievements(&mut self, stats: JsValue) -> JsValue {
-        let game_stats: GameStats = serde_wasm_bindgen::from_value(stats).unwrap();
+    pub fn check_achievements(&mut self, stats: JsValue) -> Result<JsValue, JsValue> {
+        let game_stats: GameStats = from_js(stats)?;
         let mut newly_unlocked = Vec::new();
 
         for achievement in &mut self.achievements {
             if !achievement.unlocked {
-                let progress = Self::calculate_progress(&achievement, &game_stats);
+                let progress = Self::calculate_progress(achievement, &game_stats);
                 achievement.progress = progress;
 
                 if progress >= achievement.max_progress {
@@ -58,7 +64,7 @@ impl AchievementManager {
             }
         }
 
-        serde_wasm_bindgen::to_value(&newly_unlocked).unwrap()
+        to_js(&newly_unlocked)
     }
 
     fn calculate_progress(achievement: &Achievement, stats: &GameStats) -> u32 {
@@ -37,7 +37,7 @@ pub struct AchievementCondition {
     pub max_blocks: Option<u32>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct GameStats {
     pub score: u32,
     pub lines_cleared: u32,
@@ -0,0 +1,454 @@
+use crate::Field;
+
+// ミノ1個分のマス（絶対座標）
+pub type Blocks = [(i32, i32); 4];
+
+// 盤面のマスが埋まっているかを問い合わせる共通インターフェース
+// 衝突判定・スピン判定はこれだけを使うので、JSから渡されたFieldとセッションのBitBoardの両方で動く
+pub trait Occupancy {
+    // フィールド外（壁・床・天井）は埋まっている扱い
+    fn is_filled(&self, x: i32, y: i32) -> bool;
+
+    // 置いたとき既存ブロックかフィールド外に重なるか
+    fn collides(&self, blocks: &Blocks) -> bool {
+        blocks.iter().any(|&(x, y)| self.is_filled(x, y))
+    }
+
+    // 何段下まで落とせるか
+    fn drop_distance(&self, blocks: &Blocks) -> i32 {
+        let mut distance = 0;
+        while !self.collides(&blocks.map(|(x, y)| (x, y + distance + 1))) {
+            distance += 1;
+        }
+        distance
+    }
+
+    // 置いた場合に揃う行数（盤面は変更しない）
+    fn completed_rows(&self, blocks: &Blocks) -> u32;
+}
+
+impl Occupancy for Field {
+    fn is_filled(&self, x: i32, y: i32) -> bool {
+        if x < 0 || y < 0 || (x as u32) >= self.width || (y as u32) >= self.height {
+            return true;
+        }
+        let idx = (y as u32 * self.width + x as u32) as usize;
+        self.cells.get(idx).copied().unwrap_or(0) != 0
+    }
+
+    fn completed_rows(&self, blocks: &Blocks) -> u32 {
+        let mut rows: Vec<i32> = blocks.iter().map(|&(_, y)| y).collect();
+        rows.sort_unstable();
+        rows.dedup();
+        rows.into_iter()
+            .filter(|&y| y >= 0 && (y as u32) < self.height)
+            .filter(|&y| (0..self.width as i32).all(|x| blocks.contains(&(x, y)) || self.is_filled(x, y)))
+            .count() as u32
+    }
+}
+
+// 1行の列数の上限（1行をu32のビット列で持つため）
+pub const MAX_WIDTH: u32 = 32;
+
+// 1行を1つのビット列（bit x = x列目が埋まっている）で持つフィールド
+// 衝突判定・ライン判定・落下距離はビット演算で求め、JSとの境界ではFieldに変換する
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitBoard {
+    width: u32,
+    height: u32,
+    rows: Vec<u32>,
+    // 各マスの種類とフラグ（表示用。埋まっているかはrowsで判定する）
+    cells: Vec<u8>,
+}
+
+impl BitBoard {
+    // 幅は1〜MAX_WIDTH列（JSから渡された幅はGameSessionで検証済み）
+    pub fn new(width: u32, height: u32) -> Self {
+        assert!((1..=MAX_WIDTH).contains(&width), "フィールドの幅は1〜{}列: {}", MAX_WIDTH, width);
+        Self {
+            width,
+            height,
+            rows: vec![0; height as usize],
+            cells: vec![0; (width * height) as usize],
+        }
+    }
+
+    pub fn from_field(field: &Field) -> Self {
+        let mut board = Self::new(field.width, field.height);
+        for y in 0..board.height {
+            for x in 0..board.width.min(field.width) {
+                let cell = field.cells.get((y * field.width + x) as usize).copied().unwrap_or(0);
+                board.set(x, y, cell);
+            }
+        }
+        board
+    }
+
+    pub fn to_field(&self) -> Field {
+        Field {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.clone(),
+        }
+    }
+
+    // 上からhidden段を除いた可視部分
+    pub fn visible_part(&self, hidden: u32) -> Field {
+        let hidden = hidden.min(self.height);
+        Field {
+            width: self.width,
+            height: self.height - hidden,
+            cells: self.cells[(hidden * self.width) as usize..].to_vec(),
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    // 全列が埋まった行のビット列
+    fn full_row(&self) -> u32 {
+        u32::MAX >> (MAX_WIDTH - self.width)
+    }
+
+    fn in_bounds(&self, x: i32, y: i32) -> bool {
+        x >= 0 && y >= 0 && (x as u32) < self.width && (y as u32) < self.height
+    }
+
+    pub fn cell(&self, x: u32, y: u32) -> u8 {
+        self.cells[(y * self.width + x) as usize]
+    }
+
+    pub fn set(&mut self, x: u32, y: u32, cell: u8) {
+        self.cells[(y * self.width + x) as usize] = cell;
+        let bit = 1 << x;
+        if cell == 0 {
+            self.rows[y as usize] &= !bit;
+        } else {
+            self.rows[y as usize] |= bit;
+        }
+    }
+
+    // ミノを埋め込む（フィールド外のマスは捨てる）
+    pub fn place(&mut self, blocks: &Blocks, cell: u8) {
+        for &(x, y) in blocks {
+            if self.in_bounds(x, y) {
+                self.set(x as u32, y as u32, cell);
+            }
+        }
+    }
+
+    // 揃った行を消して上の行を詰め、消した行数を返す
+    pub fn clear_full_rows(&mut self) -> u32 {
+        let full = self.full_row();
+        let width = self.width as usize;
+        let mut write = self.rows.len();
+        for read in (0..self.rows.len()).rev() {
+            if self.rows[read] == full {
+                continue;
+            }
+            write -= 1;
+            if write != read {
+                self.rows[write] = self.rows[read];
+                self.cells.copy_within(read * width..(read + 1) * width, write * width);
+            }
+        }
+        self.rows[..write].fill(0);
+        self.cells[..write * width].fill(0);
+        write as u32
+    }
+
+    // 最下段を削除して上の段を1段ずつ下ろす
+    pub fn delete_bottom_row(&mut self) {
+        if self.rows.is_empty() {
+            return;
+        }
+        let width = self.width as usize;
+        let len = self.cells.len();
+        self.rows.rotate_right(1);
+        self.rows[0] = 0;
+        self.cells.copy_within(0..len - width, width);
+        self.cells[..width].fill(0);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.iter().all(|&row| row == 0)
+    }
+}
+
+// ミノを行ごとのビット列にまとめたもの
+struct PieceRows {
+    rows: [(i32, u32); 4],
+    len: usize,
+    // 左右の壁の外にはみ出したマスがあるか（そのマスはrowsに含めない）
+    outside: bool,
+}
+
+impl PieceRows {
+    fn rows(&self) -> &[(i32, u32)] {
+        &self.rows[..self.len]
+    }
+}
+
+impl BitBoard {
+    fn piece_rows(&self, blocks: &Blocks) -> PieceRows {
+        let mut piece = PieceRows { rows: [(0, 0); 4], len: 0, outside: false };
+        for &(x, y) in blocks {
+            if x < 0 || (x as u32) >= self.width {
+                piece.outside = true;
+                continue;
+            }
+            match piece.rows[..piece.len].iter_mut().find(|(row, _)| *row == y) {
+                Some((_, mask)) => *mask |= 1 << x,
+                None => {
+                    piece.rows[piece.len] = (y, 1 << x);
+                    piece.len += 1;
+                }
+            }
+        }
+        piece
+    }
+
+    // offset段下にずらしても重ならないか（上下のフィールド外は重なる扱い）
+    fn fits(&self, piece: &PieceRows, offset: i32) -> bool {
+        !piece.outside
+            && piece.rows().iter().all(|&(y, mask)| {
+                let y = y + offset;
+                y >= 0 && (y as u32) < self.height && self.rows[y as usize] & mask == 0
+            })
+    }
+}
+
+impl Occupancy for BitBoard {
+    fn is_filled(&self, x: i32, y: i32) -> bool {
+        !self.in_bounds(x, y) || self.rows[y as usize] & (1 << x) != 0
+    }
+
+    fn collides(&self, blocks: &Blocks) -> bool {
+        !self.fits(&self.piece_rows(blocks), 0)
+    }
+
+    // 行ごとのビット列のまま1段ずつ下ろし、どれかの行が重なる手前まで
+    fn drop_distance(&self, blocks: &Blocks) -> i32 {
+        let piece = self.piece_rows(blocks);
+        let mut distance = 0;
+        while self.fits(&piece, distance + 1) {
+            distance += 1;
+        }
+        distance
+    }
+
+    // 行のビット列とミノのビット列の和が全列埋まりになる行を数える
+    fn completed_rows(&self, blocks: &Blocks) -> u32 {
+        let full = self.full_row();
+        self.piece_rows(blocks)
+            .rows()
+            .iter()
+            .filter(|&&(y, mask)| y >= 0 && (y as u32) < self.height && self.rows[y as usize] | mask == full)
+            .count() as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell;
+    use std::time::Instant;
+
+    fn board_with_rows(width: u32, height: u32, filled: &[(u32, u32)]) -> BitBoard {
+        let mut board = BitBoard::new(width, height);
+        for &(x, y) in filled {
+            board.set(x, y, 1);
+        }
+        board
+    }
+
+    #[test]
+    fn test_field_round_trip_keeps_typed_cells() {
+        let field = Field { width: 4, height: 2, cells: vec![0, 3, 0, 0, 1, cell::GARBAGE, 3 | cell::FLAG_FROM_HOLD, 7] };
+        let board = BitBoard::from_field(&field);
+        assert_eq!(board.rows, vec![0b0010, 0b1111]);
+        assert_eq!(board.to_field().cells, field.cells);
+        assert_eq!(board.visible_part(1).cells, vec![1, cell::GARBAGE, 3 | cell::FLAG_FROM_HOLD, 7]);
+    }
+
+    #[test]
+    fn test_clear_full_rows_with_typed_cells() {
+        let field = Field {
+            width: 4,
+            height: 3,
+            cells: vec![0, 2, 0, 0, 1, cell::GARBAGE, 3 | cell::FLAG_FROM_HOLD, 7, 5, 0, 0, 0],
+        };
+        let mut board = BitBoard::from_field(&field);
+        assert_eq!(board.clear_full_rows(), 1);
+        assert_eq!(board.to_field().cells, vec![0, 0, 0, 0, 0, 2, 0, 0, 5, 0, 0, 0]);
+        assert_eq!(board.rows, vec![0, 0b0010, 0b0001]);
+    }
+
+    #[test]
+    fn test_delete_bottom_row() {
+        let mut board = board_with_rows(10, 3, &[(0, 1), (5, 2)]);
+        board.delete_bottom_row();
+        assert_eq!(board.rows, vec![0, 0, 1]);
+        assert_eq!(board.cell(0, 2), 1);
+        assert_eq!(board.cell(5, 2), 0);
+    }
+
+    #[test]
+    fn test_full_width_row_uses_all_bits() {
+        let mut board = BitBoard::new(MAX_WIDTH, 2);
+        assert_eq!(board.width(), MAX_WIDTH);
+        for x in 0..MAX_WIDTH {
+            board.set(x, 1, 1);
+        }
+        assert_eq!(board.clear_full_rows(), 1);
+        assert!(board.is_empty());
+    }
+
+    #[test]
+    fn test_bit_queries_match_field() {
+        // 同じ盤面でFieldとBitBoardが同じ結果を返す
+        let mut field = Field { width: 10, height: 20, cells: vec![0; 200] };
+        for x in 0..9 {
+            field.cells[19 * 10 + x] = 1;
+        }
+        field.cells[15 * 10 + 4] = 2;
+        field.cells[17 * 10 + 7] = 3;
+        let board = BitBoard::from_field(&field);
+        for y in -2..22 {
+            for x in -2..12 {
+                assert_eq!(board.is_filled(x, y), field.is_filled(x, y), "({}, {})", x, y);
+            }
+        }
+        let pieces: [Blocks; 6] = [
+            [(9, 16), (9, 17), (9, 18), (9, 19)],
+            [(3, 0), (4, 0), (5, 0), (6, 0)],
+            [(6, 10), (7, 10), (8, 10), (7, 9)],
+            [(-1, 5), (0, 5), (1, 5), (2, 5)],
+            [(3, -1), (4, -1), (5, -1), (6, -1)],
+            [(4, -3), (4, -2), (4, -1), (4, 0)],
+        ];
+        for blocks in &pieces {
+            assert_eq!(board.collides(blocks), field.collides(blocks), "{:?}", blocks);
+            assert_eq!(board.drop_distance(blocks), field.drop_distance(blocks), "{:?}", blocks);
+            assert_eq!(board.completed_rows(blocks), field.completed_rows(blocks), "{:?}", blocks);
+        }
+        assert_eq!(board.drop_distance(&pieces[1]), 14);
+        assert_eq!(board.completed_rows(&pieces[0]), 1);
+    }
+
+    // 全ての位置・向きについて衝突・落下距離・揃う行数を求める（AI探索と同じ負荷）
+    fn search_all_placements(board: &dyn Occupancy, system: &dyn crate::RotationSystem) -> u64 {
+        let mut total = 0u64;
+        for kind in crate::TetrominoKind::ALL {
+            for rotation in 0..4 {
+                let shape = system.shape(kind, rotation);
+                for x in -2..10 {
+                    let blocks = shape.map(|(dx, dy)| (x + dx, dy));
+                    if board.collides(&blocks) {
+                        continue;
+                    }
+                    let distance = board.drop_distance(&blocks);
+                    let landed = blocks.map(|(bx, by)| (bx, by + distance));
+                    total += distance as u64 + board.completed_rows(&landed) as u64;
+                }
+            }
+        }
+        total
+    }
+
+    // CARGO_PROFILE_RELEASE_OPT_LEVEL=3 cargo test --release bench_ -- --ignored --nocapture
+    #[test]
+    #[ignore]
+    fn bench_placement_search() {
+        let mut field = Field { width: 10, height: 40, cells: vec![0; 400] };
+        for y in 30..40 {
+            for x in 0..10 {
+                if (x + y) % 7 != 0 {
+                    field.cells[y * 10 + x] = 1;
+                }
+            }
+        }
+        let board = BitBoard::from_field(&field);
+        let system = crate::RotationSystemKind::Srs.system();
+        let iterations = 2_000;
+
+        let start = Instant::now();
+        let mut expected = 0;
+        for _ in 0..iterations {
+            expected = search_all_placements(&field, system);
+        }
+        let cells_time = start.elapsed();
+
+        let start = Instant::now();
+        let mut actual = 0;
+        for _ in 0..iterations {
+            actual = search_all_placements(&board, system);
+        }
+        let bits_time = start.elapsed();
+
+        assert_eq!(actual, expected);
+        println!(
+            "placement search x{}: Field {:?}, BitBoard {:?} ({:.1}x)",
+            iterations,
+            cells_time,
+            bits_time,
+            cells_time.as_secs_f64() / bits_time.as_secs_f64()
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_line_clear() {
+        let iterations = 20_000;
+        let mut template = BitBoard::new(10, 40);
+        for y in 20..40 {
+            for x in 0..10 {
+                if y % 3 != 0 || x != 4 {
+                    template.set(x, y, 1);
+                }
+            }
+        }
+        let field = template.to_field();
+
+        // 以前のclear_lines（行ごとに作り直してsplice）と同じ処理
+        let start = Instant::now();
+        for _ in 0..iterations {
+            let width = field.width as usize;
+            let mut new_cells = vec![];
+            let mut cleared = 0;
+            for row in field.cells.chunks(width) {
+                if row.iter().all(|&c| c != 0) {
+                    cleared += 1;
+                } else {
+                    new_cells.extend_from_slice(row);
+                }
+            }
+            for _ in 0..cleared {
+                new_cells.splice(0..0, vec![0; width]);
+            }
+            std::hint::black_box(new_cells);
+        }
+        let cells_time = start.elapsed();
+
+        let start = Instant::now();
+        let mut board = template.clone();
+        for _ in 0..iterations {
+            board.clone_from(&template);
+            std::hint::black_box(board.clear_full_rows());
+        }
+        let bits_time = start.elapsed();
+
+        println!(
+            "line clear x{}: Vec<u8> {:?}, BitBoard {:?} ({:.1}x)",
+            iterations,
+            cells_time,
+            bits_time,
+            cells_time.as_secs_f64() / bits_time.as_secs_f64()
+        );
+    }
+}
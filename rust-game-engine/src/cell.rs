@@ -0,0 +1,52 @@
+use crate::TetrominoKind;
+
+// フィールドの1マスの値
+// 下位4ビット: 種類（0=空, 1〜7=ミノ(I,O,T,S,Z,J,L), 8=おじゃまブロック）
+// 上位4ビット: フラグ
+// 0以外なら埋まっているので、衝突判定・ライン消去は従来通り「0以外」で判定できる
+pub const EMPTY: u8 = 0;
+pub const GARBAGE: u8 = 8;
+// ホールドから出したミノで置かれたブロック
+pub const FLAG_FROM_HOLD: u8 = 0x10;
+
+const KIND_MASK: u8 = 0x0F;
+pub const GARBAGE_KIND: &str = "G";
+
+// ミノの種類からマスの値を作る
+pub fn piece_cell(kind: TetrominoKind, flags: u8) -> u8 {
+    (kind.index() as u8 + 1) | flags
+}
+
+// マスの種類（空ならNone、おじゃまブロックは"G"）
+pub fn cell_kind(cell: u8) -> Option<&'static str> {
+    match cell & KIND_MASK {
+        EMPTY => None,
+        GARBAGE => Some(GARBAGE_KIND),
+        code => TetrominoKind::ALL.get(code as usize - 1).map(|k| k.name()).or(Some(GARBAGE_KIND)),
+    }
+}
+
+pub fn has_flag(cell: u8, flag: u8) -> bool {
+    cell & flag != 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_piece_cell_round_trip() {
+        for kind in TetrominoKind::ALL {
+            let cell = piece_cell(kind, 0);
+            assert_ne!(cell, EMPTY);
+            assert_ne!(cell & KIND_MASK, GARBAGE);
+            assert_eq!(cell_kind(cell), Some(kind.name()));
+            let held = piece_cell(kind, FLAG_FROM_HOLD);
+            assert_eq!(cell_kind(held), Some(kind.name()));
+            assert!(has_flag(held, FLAG_FROM_HOLD));
+            assert!(!has_flag(cell, FLAG_FROM_HOLD));
+        }
+        assert_eq!(cell_kind(EMPTY), None);
+        assert_eq!(cell_kind(GARBAGE), Some(GARBAGE_KIND));
+    }
+}
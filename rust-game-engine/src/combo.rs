@@ -0,0 +1,92 @@
+// コンボ1回あたりのボーナス
+pub const COMBO_BONUS: u32 = 50;
+// Back-to-Back成立時、ライン消去点とスピンボーナスに掛ける倍率（1.5倍）
+pub const B2B_MULTIPLIER_NUM: u32 = 3;
+pub const B2B_MULTIPLIER_DEN: u32 = 2;
+
+// 1回の固定後のコンボ・B2B状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComboUpdate {
+    // 連続消去の回数-1（2回連続で1コンボ）。消去なしなら0
+    pub combo: u32,
+    // 今回の消去がBack-to-Backとして成立したか
+    pub back_to_back: bool,
+}
+
+// コンボとBack-to-Backの追跡
+// - コンボ: ライン消去が連続した回数。消去なしで固定するとリセット
+// - B2B: テトリスかスピン消去が、通常消去を挟まずに続いた回数
+//   消去なしの固定ではB2Bは途切れない
+#[derive(Debug, Default)]
+pub struct ComboState {
+    chain: u32,
+    max_combo: u32,
+    b2b_ready: bool,
+    b2b: u32,
+}
+
+impl ComboState {
+    pub fn combo(&self) -> u32 {
+        self.chain.saturating_sub(1)
+    }
+
+    pub fn max_combo(&self) -> u32 {
+        self.max_combo
+    }
+
+    // 現在のB2B連続数（0ならB2Bなし）
+    pub fn b2b(&self) -> u32 {
+        self.b2b
+    }
+
+    // difficult: テトリスまたはスピンによる消去
+    pub fn on_lock(&mut self, lines: u32, difficult: bool) -> ComboUpdate {
+        if lines == 0 {
+            self.chain = 0;
+            return ComboUpdate { combo: 0, back_to_back: false };
+        }
+        self.chain += 1;
+        self.max_combo = self.max_combo.max(self.combo());
+
+        let back_to_back = difficult && self.b2b_ready;
+        if back_to_back {
+            self.b2b += 1;
+        } else if !difficult {
+            self.b2b = 0;
+        }
+        self.b2b_ready = difficult;
+        ComboUpdate { combo: self.combo(), back_to_back }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_combo_counts_consecutive_clears() {
+        let mut state = ComboState::default();
+        assert_eq!(state.on_lock(1, false).combo, 0);
+        assert_eq!(state.on_lock(1, false).combo, 1);
+        assert_eq!(state.on_lock(2, false).combo, 2);
+        assert_eq!(state.on_lock(0, false).combo, 0);
+        assert_eq!(state.combo(), 0);
+        assert_eq!(state.max_combo(), 2);
+    }
+
+    #[test]
+    fn test_back_to_back_chain() {
+        let mut state = ComboState::default();
+        assert!(!state.on_lock(4, true).back_to_back);
+        // 消去なしの固定ではB2Bは途切れない
+        state.on_lock(0, false);
+        assert!(state.on_lock(2, true).back_to_back);
+        assert_eq!(state.b2b(), 1);
+        assert!(state.on_lock(4, true).back_to_back);
+        assert_eq!(state.b2b(), 2);
+        // 通常消去で途切れる
+        assert!(!state.on_lock(1, false).back_to_back);
+        assert_eq!(state.b2b(), 0);
+        assert!(!state.on_lock(4, true).back_to_back);
+    }
+}
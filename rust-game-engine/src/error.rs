@@ -0,0 +1,92 @@
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+// JSとの境界で返すエラー
+// JS側には name="EngineError" のErrorとして投げられ、kind（種類）とvalue（不正だった値）を持つ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EngineError {
+    // JSから渡された値の形式が不正
+    InvalidInput(String),
+    // 未知のミノの種類
+    UnknownPieceKind(String),
+    // 未知の移動・回転方向
+    InvalidDirection(String),
+    // ゲームオーバー後の操作
+    GameOver,
+    // 結果をJSの値に変換できなかった
+    Serialization(String),
+}
+
+impl EngineError {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            EngineError::InvalidInput(_) => "InvalidInput",
+            EngineError::UnknownPieceKind(_) => "UnknownPieceKind",
+            EngineError::InvalidDirection(_) => "InvalidDirection",
+            EngineError::GameOver => "GameOver",
+            EngineError::Serialization(_) => "Serialization",
+        }
+    }
+
+    // 不正だった値（ミノの種類・方向）
+    fn value(&self) -> Option<&str> {
+        match self {
+            EngineError::UnknownPieceKind(value) | EngineError::InvalidDirection(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for EngineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EngineError::InvalidInput(message) => write!(f, "不正な入力: {}", message),
+            EngineError::UnknownPieceKind(kind) => write!(f, "未知のミノ: {:?}", kind),
+            EngineError::InvalidDirection(dir) => write!(f, "未知の方向: {:?}", dir),
+            EngineError::GameOver => write!(f, "ゲームオーバー後は操作できません"),
+            EngineError::Serialization(message) => write!(f, "変換に失敗: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for EngineError {}
+
+impl From<EngineError> for JsValue {
+    fn from(error: EngineError) -> Self {
+        let js_error = js_sys::Error::new(&error.to_string());
+        js_error.set_name("EngineError");
+        // 通常のオブジェクトへの設定なので失敗しない
+        let _ = js_sys::Reflect::set(&js_error, &"kind".into(), &error.kind().into());
+        if let Some(value) = error.value() {
+            let _ = js_sys::Reflect::set(&js_error, &"value".into(), &value.into());
+        }
+        js_error.into()
+    }
+}
+
+// Rustの値をJSの値に変換
+pub(crate) fn to_js<T: Serialize + ?Sized>(value: &T) -> Result<JsValue, JsValue> {
+    serde_wasm_bindgen::to_value(value).map_err(|e| EngineError::Serialization(e.to_string()).into())
+}
+
+// JSから渡された値を読み取る
+pub(crate) fn from_js<T: DeserializeOwned>(value: JsValue) -> Result<T, EngineError> {
+    serde_wasm_bindgen::from_value(value).map_err(|e| EngineError::InvalidInput(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_kind_and_value() {
+        let error = EngineError::InvalidDirection("up".to_string());
+        assert_eq!(error.kind(), "InvalidDirection");
+        assert_eq!(error.value(), Some("up"));
+        assert!(error.to_string().contains("up"));
+        assert_eq!(EngineError::GameOver.value(), None);
+    }
+}
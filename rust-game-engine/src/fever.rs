@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+
+pub const FEVER_BLOCKS_NEEDED: u32 = 20;
+pub const FEVER_DURATION_MS: u32 = 20_000;
+pub const FEVER_SCORE_MULTIPLIER: u32 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FeverEvent {
+    Started,
+    Ended,
+}
+
+// UI表示用のフィーバー状態
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeverState {
+    pub active: bool,
+    pub remaining_ms: u32,
+    // 次のフィーバーまでの配置数の進捗
+    pub placements: u32,
+    pub placements_needed: u32,
+    pub multiplier: u32,
+}
+
+// フィーバーモード
+// ブロックを規定数配置すると発動し、エンジンの時間で一定時間継続する
+// フィーバー中の配置は次のフィーバーの配置数に数えない
+#[derive(Default)]
+pub struct Fever {
+    placements: u32,
+    remaining_ms: u32,
+    count: u32,
+}
+
+impl Fever {
+    pub fn is_active(&self) -> bool {
+        self.remaining_ms > 0
+    }
+
+    pub fn multiplier(&self) -> u32 {
+        if self.is_active() { FEVER_SCORE_MULTIPLIER } else { 1 }
+    }
+
+    // このゲームでフィーバーが発動した回数
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    pub fn on_placement(&mut self) -> Option<FeverEvent> {
+        if self.is_active() {
+            return None;
+        }
+        self.placements += 1;
+        if self.placements < FEVER_BLOCKS_NEEDED {
+            return None;
+        }
+        self.placements = 0;
+        self.remaining_ms = FEVER_DURATION_MS;
+        self.count += 1;
+        Some(FeverEvent::Started)
+    }
+
+    pub fn advance(&mut self, dt_ms: u32) -> Option<FeverEvent> {
+        if !self.is_active() {
+            return None;
+        }
+        self.remaining_ms = self.remaining_ms.saturating_sub(dt_ms);
+        if self.is_active() { None } else { Some(FeverEvent::Ended) }
+    }
+
+    pub fn state(&self) -> FeverState {
+        FeverState {
+            active: self.is_active(),
+            remaining_ms: self.remaining_ms,
+            placements: self.placements,
+            placements_needed: FEVER_BLOCKS_NEEDED,
+            multiplier: self.multiplier(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fever_starts_after_placements_and_ends_after_duration() {
+        let mut fever = Fever::default();
+        for _ in 0..FEVER_BLOCKS_NEEDED - 1 {
+            assert_eq!(fever.on_placement(), None);
+        }
+        assert_eq!(fever.on_placement(), Some(FeverEvent::Started));
+        assert!(fever.is_active());
+        assert_eq!(fever.multiplier(), FEVER_SCORE_MULTIPLIER);
+        assert_eq!(fever.count(), 1);
+
+        // フィーバー中の配置は数えない
+        fever.on_placement();
+        assert_eq!(fever.state().placements, 0);
+
+        assert_eq!(fever.advance(FEVER_DURATION_MS - 1), None);
+        assert_eq!(fever.advance(1), Some(FeverEvent::Ended));
+        assert_eq!(fever.multiplier(), 1);
+        assert_eq!(fever.advance(1000), None);
+    }
+}
@@ -1,11 +1,18 @@
 use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::error::{to_js, EngineError};
+use crate::session::GameSession;
+use crate::{MoveDirection, RotationDirection, Tetromino, TetrominoKind};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameState {
+    // 可視部分のフィールド（行ごと）
     pub field: Vec<Vec<u8>>,
     pub current_tetromino: Tetromino,
-    pub next_tetromino: Tetromino,
+    // NEXTに表示するミノ（先頭が次に出るミノ）
+    pub next_queue: Vec<TetrominoKind>,
+    pub hold_piece: Option<TetrominoKind>,
     pub score: u32,
     pub level: u32,
     pub lines_cleared: u32,
@@ -13,142 +20,236 @@ pub struct GameState {
     pub paused: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Tetromino {
-    pub shape: Vec<Vec<u8>>,
-    pub x: i32,
-    pub y: i32,
-    pub rotation: u8,
-    pub color: u8,
-}
-
+// JS向けのクラスAPI。ゲームの状態と処理はGameSessionに任せる
 #[wasm_bindgen]
 pub struct GameEngine {
-    state: GameState,
+    session: GameSession,
+    paused: bool,
+}
+
+impl Default for GameEngine {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[wasm_bindgen]
 impl GameEngine {
+    // 可視10x20のフィールドで最初のミノを出した状態から始める
     #[wasm_bindgen(constructor)]
     pub fn new() -> Self {
-        Self {
-            state: GameState {
-                field: vec![vec![0; 10]; 20],
-                current_tetromino: Self::create_tetromino(),
-                next_tetromino: Self::create_tetromino(),
-                score: 0,
-                level: 1,
-                lines_cleared: 0,
-                game_over: false,
-                paused: false,
-            }
-        }
+        // 固定の引数なのでエラーにはならない
+        let session = GameSession::try_new(10, 20, None, None, None, None).unwrap_or_default();
+        Self::from_session(session)
     }
 
-    pub fn get_state(&self) -> JsValue {
-        serde_wasm_bindgen::to_value(&self.state).unwrap()
+    pub fn get_state(&self) -> Result<JsValue, JsValue> {
+        to_js(&self.state())
     }
 
-    pub fn move_tetromino(&mut self, direction: &str) -> JsValue {
-        match direction {
-            "left" => self.move_left(),
-            "right" => self.move_right(),
-            "down" => self.move_down(),
-            _ => {}
-        }
+    // direction: "left" | "right" | "down"（それ以外はエラー）
+    pub fn move_tetromino(&mut self, direction: &str) -> Result<JsValue, JsValue> {
+        let direction = MoveDirection::parse(direction)?;
+        self.input(|s| {
+            s.move_active(direction);
+        })?;
         self.get_state()
     }
 
-    pub fn rotate_tetromino(&mut self, direction: &str) -> JsValue {
-        match direction {
-            "clockwise" => self.rotate_clockwise(),
-            "counterclockwise" => self.rotate_counterclockwise(),
-            _ => {}
-        }
+    // direction: "clockwise" | "counterclockwise" | "180"（"right" / "left" も可、それ以外はエラー）
+    pub fn rotate_tetromino(&mut self, direction: &str) -> Result<JsValue, JsValue> {
+        let direction = RotationDirection::parse(direction)?;
+        self.input(|s| {
+            s.rotate_active(direction);
+        })?;
         self.get_state()
     }
 
-    pub fn drop_tetromino(&mut self) -> JsValue {
-        while self.move_down() {}
+    // ハードドロップして固定し、次のミノを出す
+    pub fn drop_tetromino(&mut self) -> Result<JsValue, JsValue> {
+        self.input(|s| {
+            s.hard_drop_active();
+        })?;
         self.get_state()
     }
 
-    pub fn update(&mut self) -> JsValue {
-        if !self.state.game_over && !self.state.paused {
-            if !self.move_down() {
-                self.lock_tetromino();
-                self.clear_lines();
-                self.spawn_new_tetromino();
-            }
-        }
+    pub fn hold_tetromino(&mut self) -> Result<JsValue, JsValue> {
+        self.input(|s| {
+            s.hold_current();
+        })?;
         self.get_state()
     }
 
-    fn create_tetromino() -> Tetromino {
-        // 簡易的なテトロミノ作成（後で実装）
-        Tetromino {
-            shape: vec![vec![1, 1], vec![1, 1]], // I字型
-            x: 4,
-            y: 0,
-            rotation: 0,
-            color: 1,
-        }
+    // 1段分の自然落下。接地中は1段分の時間だけ固定猶予を進め、尽きたら固定して次のミノを出す
+    pub fn update(&mut self) -> Result<JsValue, JsValue> {
+        self.when_running(|s| {
+            s.gravity_step();
+        });
+        self.get_state()
     }
 
-    fn move_left(&mut self) -> bool {
-        self.state.current_tetromino.x -= 1;
-        if !self.is_valid_position() {
-            self.state.current_tetromino.x += 1;
-            false
-        } else {
-            true
-        }
+    // 経過時間(ms)で進める（レベルに応じた落下速度・固定猶予を使う）
+    pub fn tick(&mut self, dt_ms: u32) -> Result<JsValue, JsValue> {
+        self.when_running(|s| {
+            s.tick_active(dt_ms);
+        });
+        self.get_state()
     }
 
-    fn move_right(&mut self) -> bool {
-        self.state.current_tetromino.x += 1;
-        if !self.is_valid_position() {
-            self.state.current_tetromino.x -= 1;
-            false
-        } else {
-            true
-        }
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
     }
+}
 
-    fn move_down(&mut self) -> bool {
-        self.state.current_tetromino.y += 1;
-        if !self.is_valid_position() {
-            self.state.current_tetromino.y -= 1;
-            false
-        } else {
-            true
+impl GameEngine {
+    // 一時停止中は入力も時間も進めない
+    fn when_running(&mut self, action: impl FnOnce(&mut GameSession)) {
+        if !self.paused {
+            action(&mut self.session);
         }
     }
 
-    fn rotate_clockwise(&mut self) -> bool {
-        // 回転ロジック（後で実装）
-        true
-    }
-
-    fn rotate_counterclockwise(&mut self) -> bool {
-        // 回転ロジック（後で実装）
-        true
-    }
-
-    fn is_valid_position(&self) -> bool {
-        // 衝突検出ロジック（後で実装）
-        true
-    }
-
-    fn lock_tetromino(&mut self) {
-        // テトロミノを固定するロジック（後で実装）
-    }
-
-    fn clear_lines(&mut self) {
-        // ライン消去ロジック（後で実装）
+    // 入力の操作はGameSessionと同じくゲームオーバー後はエラーにする
+    fn input(&mut self, action: impl FnOnce(&mut GameSession)) -> Result<(), EngineError> {
+        self.session.ensure_playing()?;
+        self.when_running(action);
+        Ok(())
+    }
+
+    fn from_session(mut session: GameSession) -> Self {
+        let kind = session.next_tetromino_kind();
+        session.spawn_piece(kind);
+        Self { session, paused: false }
+    }
+
+    fn state(&self) -> GameState {
+        let session = &self.session;
+        let visible = session.visible_field();
+        GameState {
+            field: visible.cells.chunks(visible.width as usize).map(<[u8]>::to_vec).collect(),
+            current_tetromino: session.current_tetromino().clone(),
+            next_queue: session.next_queue_kinds(),
+            hold_piece: session.hold_piece(),
+            score: session.get_score(),
+            level: session.get_level(),
+            lines_cleared: session.get_lines_cleared(),
+            game_over: session.is_game_over(),
+            paused: self.paused,
+        }
     }
+}
 
-    fn spawn_new_tetromino(&mut self) {
-        // 新しいテトロミノを生成するロジック（後で実装）
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 可視10x20、バッファなし
+    fn engine() -> GameEngine {
+        let session = GameSession::try_new(10, 20, Some(1), None, None, Some(0)).unwrap();
+        GameEngine::from_session(session)
+    }
+
+    fn filled_count(state: &GameState) -> usize {
+        state.field.iter().flatten().filter(|&&c| c != 0).count()
+    }
+
+    #[test]
+    fn test_update_falls_then_locks_and_spawns() {
+        let mut engine = engine();
+        let start = engine.state();
+        assert_eq!(start.field.len(), 20);
+        assert_eq!(start.field[0].len(), 10);
+        let first_kind = start.current_tetromino.kind;
+        let next = start.next_queue[0];
+
+        engine.when_running(|s| {
+            s.gravity_step();
+        });
+        assert_eq!(engine.state().current_tetromino.y, start.current_tetromino.y + 1);
+
+        // 底に着くまで落とし、接地した次のupdateで固定される
+        let mut steps = 0;
+        while filled_count(&engine.state()) == 0 {
+            engine.when_running(|s| {
+                s.gravity_step();
+            });
+            steps += 1;
+            assert!(steps < 30);
+        }
+        let state = engine.state();
+        assert_eq!(filled_count(&state), 4);
+        assert_eq!(state.current_tetromino.kind, next);
+        assert!(state.field[19].iter().any(|&c| crate::cell::cell_kind(c) == Some(first_kind.name())));
+        assert!(!state.game_over);
+    }
+
+    #[test]
+    fn test_update_waits_for_lock_delay_on_ground() {
+        let mut engine = engine();
+        // レベル1は1段1秒なので、猶予2.5秒なら接地後3回目のupdateで固定される
+        engine.session.set_lock_delay(2500, 15);
+        let mut grounded_steps = 0;
+        for _ in 0..30 {
+            let result = engine.session.gravity_step();
+            if !result.moved {
+                grounded_steps += 1;
+            }
+            if result.locked {
+                break;
+            }
+            assert_eq!(filled_count(&engine.state()), 0);
+        }
+        assert_eq!(grounded_steps, 3);
+        assert_eq!(filled_count(&engine.state()), 4);
+    }
+
+    #[test]
+    fn test_rotation_uses_rotation_system() {
+        let mut engine = engine();
+        let rotation = engine.state().current_tetromino.rotation;
+        engine.when_running(|s| {
+            s.rotate_active(RotationDirection::Clockwise);
+        });
+        assert_eq!(engine.state().current_tetromino.rotation, (rotation + 1) % 4);
+        engine.when_running(|s| {
+            s.rotate_active(RotationDirection::CounterClockwise);
+        });
+        assert_eq!(engine.state().current_tetromino.rotation, rotation);
+    }
+
+    #[test]
+    fn test_paused_engine_ignores_update() {
+        let mut engine = engine();
+        engine.set_paused(true);
+        let before = engine.state().current_tetromino.y;
+        engine.when_running(|s| {
+            s.gravity_step();
+        });
+        assert_eq!(engine.state().current_tetromino.y, before);
+        assert!(engine.state().paused);
+
+        engine.set_paused(false);
+        engine.when_running(|s| {
+            s.gravity_step();
+        });
+        assert_eq!(engine.state().current_tetromino.y, before + 1);
+    }
+
+    #[test]
+    fn test_input_after_game_over_is_error() {
+        let mut engine = engine();
+        // 中央に積み上げてゲームオーバーにする
+        let mut drops = 0;
+        while !engine.state().game_over {
+            engine.input(|s| {
+                s.hard_drop_active();
+            }).unwrap();
+            drops += 1;
+            assert!(drops < 100);
+        }
+        let result = engine.input(|s| {
+            s.move_active(MoveDirection::Left);
+        });
+        assert_eq!(result, Err(EngineError::GameOver));
     }
-} 
\ No newline at end of file
+}
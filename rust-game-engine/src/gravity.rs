@@ -0,0 +1,143 @@
+pub const LINES_PER_LEVEL: u32 = 10;
+// 20G（1フレームで20行落下）が上限
+pub const MAX_GRAVITY: f64 = 20.0;
+const FRAME_MS: f64 = 1000.0 / 60.0;
+// 浮動小数の丸め誤差で1行落ち損ねないための許容誤差
+const EPSILON: f64 = 1e-9;
+// カーブはこのレベルで20Gを超えるので、以降は同じ速度にする
+// （レベル116以降は式の底が負になり、落下しなくなるため）
+const CURVE_MAX_LEVEL: u32 = 20;
+
+// ガイドラインの落下速度カーブ: (0.8 - (level-1)*0.007)^(level-1) 秒/行
+pub fn seconds_per_row(level: u32) -> f64 {
+    let n = level.clamp(1, CURVE_MAX_LEVEL) as f64 - 1.0;
+    (0.8 - n * 0.007).powf(n)
+}
+
+// 1フレーム(1/60秒)あたりの落下行数（G）
+pub fn gravity_for_level(level: u32) -> f64 {
+    (1.0 / (seconds_per_row(level) * 60.0)).min(MAX_GRAVITY)
+}
+
+// レベルと自然落下の管理
+// 経過時間から1行未満の端数を蓄積し、整数行ぶんだけ落下させる
+pub struct Gravity {
+    start_level: u32,
+    level: u32,
+    lines: u32,
+    accumulator: f64,
+}
+
+impl Default for Gravity {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl Gravity {
+    pub fn new(start_level: u32) -> Self {
+        let start_level = start_level.max(1);
+        Self {
+            start_level,
+            level: start_level,
+            lines: 0,
+            accumulator: 0.0,
+        }
+    }
+
+    pub fn start_level(&self) -> u32 {
+        self.start_level
+    }
+
+    pub fn level(&self) -> u32 {
+        self.level
+    }
+
+    pub fn lines(&self) -> u32 {
+        self.lines
+    }
+
+    // 消去ライン数を加算し、10ライン毎にレベルアップ
+    pub fn add_lines(&mut self, cleared: u32) {
+        self.lines += cleared;
+        self.level = self.level.max(self.start_level + self.lines / LINES_PER_LEVEL);
+    }
+
+    // 経過時間ぶんの落下行数を返す（端数は次回に持ち越す）
+    pub fn advance(&mut self, dt_ms: u32) -> u32 {
+        self.accumulator += gravity_for_level(self.level) * dt_ms as f64 / FRAME_MS;
+        let rows = (self.accumulator + EPSILON).floor();
+        self.accumulator = (self.accumulator - rows).max(0.0);
+        rows as u32
+    }
+
+    // 1行落ちるのにかかる時間(ms)。1フレームより速い落下も1フレームとして扱う
+    pub fn row_interval_ms(&self) -> u32 {
+        (seconds_per_row(self.level) * 1000.0).max(FRAME_MS).round() as u32
+    }
+
+    // 接地中は端数を捨てる（着地後に溜まった分で一気に落ちないように）
+    pub fn reset_accumulator(&mut self) {
+        self.accumulator = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_one_is_one_second_per_row() {
+        assert!((seconds_per_row(1) - 1.0).abs() < 1e-9);
+        let mut gravity = Gravity::new(1);
+        assert_eq!(gravity.advance(999), 0);
+        assert_eq!(gravity.advance(1), 1);
+    }
+
+    #[test]
+    fn test_curve_speeds_up_and_caps_at_20g() {
+        for level in 1..20 {
+            assert!(seconds_per_row(level + 1) < seconds_per_row(level));
+        }
+        assert_eq!(gravity_for_level(30), MAX_GRAVITY);
+        let mut gravity = Gravity::new(30);
+        assert_eq!(gravity.advance(17), 20);
+    }
+
+    #[test]
+    fn test_levels_past_curve_stay_at_20g() {
+        for level in [115, 116, 117, 200, u32::MAX] {
+            assert!(seconds_per_row(level) > 0.0, "{}", level);
+            assert_eq!(gravity_for_level(level), MAX_GRAVITY, "{}", level);
+            let mut gravity = Gravity::new(level);
+            assert_eq!(gravity.advance(1000), 1200, "{}", level);
+        }
+    }
+
+    #[test]
+    fn test_row_interval_follows_level() {
+        assert_eq!(Gravity::new(1).row_interval_ms(), 1000);
+        assert_eq!(Gravity::new(2).row_interval_ms(), 793);
+        assert_eq!(Gravity::new(30).row_interval_ms(), 17);
+    }
+
+    #[test]
+    fn test_fractional_rows_accumulate() {
+        // レベル2は約0.793秒/行
+        let mut gravity = Gravity::new(2);
+        let total: u32 = (0..100).map(|_| gravity.advance(16)).sum();
+        assert_eq!(total, 2);
+    }
+
+    #[test]
+    fn test_level_up_every_ten_lines() {
+        let mut gravity = Gravity::new(1);
+        gravity.add_lines(4);
+        assert_eq!(gravity.level(), 1);
+        gravity.add_lines(6);
+        assert_eq!(gravity.level(), 2);
+        let mut fast_start = Gravity::new(5);
+        fast_start.add_lines(10);
+        assert_eq!(fast_start.level(), 6);
+    }
+}
@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+
+use crate::TetrominoKind;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HoldRejection {
+    // 次の固定までホールド済み
+    AlreadyUsed,
+    // コストを支払えなかった
+    CostNotPaid,
+    // ゲームオーバー後
+    GameOver,
+}
+
+// ホールド枠
+// ガイドライン通り、一度ホールドしたら次のミノが固定されるまで再ホールド不可
+pub struct Hold {
+    piece: Option<TetrominoKind>,
+    used: bool,
+    count: u32,
+}
+
+impl Default for Hold {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hold {
+    pub fn new() -> Self {
+        Self {
+            piece: None,
+            used: false,
+            count: 0,
+        }
+    }
+
+    pub fn piece(&self) -> Option<TetrominoKind> {
+        self.piece
+    }
+
+    pub fn can_hold(&self) -> bool {
+        !self.used
+    }
+
+    // このゲームでホールドした回数
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    // currentをホールドし、代わりに出すミノを返す（Noneの場合はNEXTから出す）
+    pub fn hold(&mut self, current: TetrominoKind) -> Result<Option<TetrominoKind>, HoldRejection> {
+        if self.used {
+            return Err(HoldRejection::AlreadyUsed);
+        }
+        self.used = true;
+        self.count += 1;
+        Ok(self.piece.replace(current))
+    }
+
+    // ミノ固定時に呼ぶ
+    pub fn on_lock(&mut self) {
+        self.used = false;
+    }
+
+    // 新しいゲーム用に中身を空にする
+    pub fn reset(&mut self) {
+        self.piece = None;
+        self.used = false;
+        self.count = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hold_once_per_lock() {
+        let mut hold = Hold::new();
+        assert_eq!(hold.hold(TetrominoKind::T), Ok(None));
+        assert_eq!(hold.piece(), Some(TetrominoKind::T));
+        assert!(!hold.can_hold());
+        assert_eq!(hold.hold(TetrominoKind::I), Err(HoldRejection::AlreadyUsed));
+        hold.on_lock();
+        assert_eq!(hold.hold(TetrominoKind::I), Ok(Some(TetrominoKind::T)));
+        assert_eq!(hold.piece(), Some(TetrominoKind::I));
+    }
+}
@@ -1,275 +1,115 @@
 use wasm_bindgen::prelude::*;
 use serde::{Serialize, Deserialize};
-use serde_wasm_bindgen;
 use std::cell::RefCell;
-use rand::Rng;
-use std::collections::HashMap;
+
+pub mod achievement;
+mod bitboard;
+mod cell;
+mod combo;
+mod error;
+mod fever;
+pub mod game;
+mod gravity;
+mod hold;
+mod lock_delay;
+mod points;
+mod queue;
+mod randomizer;
+mod rank;
+mod rotation;
+mod scoring;
+mod session;
+mod tetromino;
+
+pub use bitboard::{BitBoard, Blocks, Occupancy};
+pub use error::EngineError;
+use error::{from_js, to_js};
+pub use hold::HoldRejection;
+pub use fever::{FeverEvent, FeverState};
+pub use points::{InsufficientPoints, PointsSource, PointsTransaction};
+pub use randomizer::{Randomizer, RandomizerKind};
+pub use rank::{RankInfo, RankPromotion};
+pub use rotation::{RotationSystem, RotationSystemKind};
+pub use scoring::{ClearType, ScoreAward};
+pub use session::{GameSession, HoldResult, MoveResult, PointsActionResult};
+pub use tetromino::TetrominoKind;
 
 thread_local! {
-    static FIELD: RefCell<Field> = RefCell::new(Field {
-        width: 10,
-        height: 20,
-        cells: vec![0; 200],
-    });
-    static TETROMINO: RefCell<Tetromino> = RefCell::new(Tetromino {
-        kind: "I".to_string(),
-        x: 4,
-        y: 0,
-        rotation: 0,
-    });
-    static LAST_TSPIN: RefCell<TSpinType> = RefCell::new(TSpinType::None);
-    static LAST_WALLKICK: RefCell<bool> = RefCell::new(false);
-    static LAST_KICK_INDEX: RefCell<i32> = RefCell::new(0);
-}
-
-const TETROMINO_TYPES: [&str; 7] = ["I", "O", "T", "S", "Z", "J", "L"];
-
-// テトリミノ形状・回転ごとの4マス相対座標テーブル
-const TETROMINO_SHAPES: &[(&str, [[(i32, i32); 4]; 4])] = &[
-    // Iミノ
-    ("I", [
-        [(0, 1), (1, 1), (2, 1), (3, 1)], // 0度
-        [(2, 0), (2, 1), (2, 2), (2, 3)], // 90度
-        [(0, 2), (1, 2), (2, 2), (3, 2)], // 180度
-        [(1, 0), (1, 1), (1, 2), (1, 3)], // 270度
-    ]),
-    // Oミノ
-    ("O", [
-        [(1, 0), (2, 0), (1, 1), (2, 1)], // 0度
-        [(1, 0), (2, 0), (1, 1), (2, 1)], // 90度
-        [(1, 0), (2, 0), (1, 1), (2, 1)], // 180度
-        [(1, 0), (2, 0), (1, 1), (2, 1)], // 270度
-    ]),
-    // Tミノ
-    ("T", [
-        [(1, 0), (0, 1), (1, 1), (2, 1)], // 0度
-        [(1, 0), (1, 1), (2, 1), (1, 2)], // 90度
-        [(0, 1), (1, 1), (2, 1), (1, 2)], // 180度
-        [(1, 0), (0, 1), (1, 1), (1, 2)], // 270度
-    ]),
-    // Sミノ
-    ("S", [
-        [(1, 0), (2, 0), (0, 1), (1, 1)], // 0度
-        [(1, 0), (1, 1), (2, 1), (2, 2)], // 90度
-        [(1, 1), (2, 1), (0, 2), (1, 2)], // 180度
-        [(0, 0), (0, 1), (1, 1), (1, 2)], // 270度
-    ]),
-    // Zミノ
-    ("Z", [
-        [(0, 0), (1, 0), (1, 1), (2, 1)], // 0度
-        [(2, 0), (1, 1), (2, 1), (1, 2)], // 90度
-        [(0, 1), (1, 1), (1, 2), (2, 2)], // 180度
-        [(1, 0), (0, 1), (1, 1), (0, 2)], // 270度
-    ]),
-    // Jミノ
-    ("J", [
-        [(0, 0), (0, 1), (1, 1), (2, 1)], // 0度
-        [(1, 0), (2, 0), (1, 1), (1, 2)], // 90度
-        [(0, 1), (1, 1), (2, 1), (2, 2)], // 180度
-        [(1, 0), (1, 1), (0, 2), (1, 2)], // 270度
-    ]),
-    // Lミノ
-    ("L", [
-        [(2, 0), (0, 1), (1, 1), (2, 1)], // 0度
-        [(1, 0), (1, 1), (1, 2), (2, 2)], // 90度
-        [(0, 1), (1, 1), (2, 1), (0, 2)], // 180度
-        [(0, 0), (1, 0), (1, 1), (1, 2)], // 270度
-    ]),
-];
+    // 従来のフリー関数が操作するデフォルトのセッション
+    static DEFAULT_SESSION: RefCell<GameSession> = RefCell::new(GameSession::default());
+}
+
+fn with_session<R>(f: impl FnOnce(&mut GameSession) -> R) -> R {
+    DEFAULT_SESSION.with(|s| f(&mut s.borrow_mut()))
+}
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Field {
     pub width: u32,
     pub height: u32,
+    // 各マスの種類とフラグ（エンコードはcell.rsを参照、0は空）
     pub cells: Vec<u8>,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Tetromino {
-    pub kind: String,
+    pub kind: TetrominoKind,
     pub x: i32,
     pub y: i32,
     pub rotation: u8,
 }
 
-#[derive(Clone, Copy)]
-struct KickOffset { x: i32, y: i32 }
-
-type KickTable = HashMap<(u8, u8), Vec<KickOffset>>;
-
-fn random_tetromino_kind() -> String {
-    let mut rng = rand::thread_rng();
-    let idx = rng.gen_range(0..TETROMINO_TYPES.len());
-    TETROMINO_TYPES[idx].to_string()
+// ゲームオーバーの種類（ガイドライン準拠）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameOverReason {
+    // 出現位置が既存ブロックと重なった
+    BlockOut,
+    // ミノが全て可視領域より上で固定された
+    LockOut,
 }
 
-#[wasm_bindgen]
-pub fn init_field(width: u32, height: u32) -> JsValue {
-    FIELD.with(|f| {
-        *f.borrow_mut() = Field {
-            width,
-            height,
-            cells: vec![0; (width * height) as usize],
-        };
-        serde_wasm_bindgen::to_value(&*f.borrow()).unwrap()
-    })
-}
+// 可視領域の上に置く非表示のバッファ段数（ガイドライン準拠）
+// フィールドはバッファを含めた全体で、y < バッファ段数 の行は画面に表示しない
+const DEFAULT_BUFFER_ROWS: u32 = 20;
 
-#[wasm_bindgen]
-pub fn spawn_tetromino() -> JsValue {
-    TETROMINO.with(|t| {
-        let kind = random_tetromino_kind();
-        *t.borrow_mut() = Tetromino {
-            kind,
-            x: 4,
-            y: 0,
-            rotation: 0,
-        };
-        // スピン判定状態をリセット
-        LAST_TSPIN.with(|ts| *ts.borrow_mut() = TSpinType::None);
-        LAST_WALLKICK.with(|w| *w.borrow_mut() = false);
-        LAST_KICK_INDEX.with(|k| *k.borrow_mut() = 0);
-        serde_wasm_bindgen::to_value(&*t.borrow()).unwrap()
-    })
+// 衝突判定（フィールド外も衝突扱い）
+fn check_collision(system: &dyn RotationSystem, field: &dyn Occupancy, tetromino: &Tetromino) -> bool {
+    field.collides(&tetromino_blocks(system, tetromino))
 }
 
-// 衝突判定の雛形
-fn check_collision(field: &Field, tetromino: &Tetromino) -> bool {
-    let blocks = get_tetromino_blocks(&tetromino.kind, tetromino.x, tetromino.y, tetromino.rotation);
-    
-    for (x, y) in blocks {
-        // フィールド外チェック
-        if x < 0 || y < 0 || (x as u32) >= field.width || (y as u32) >= field.height {
-            return true;
-        }
-        
-        // 既存ブロックとの衝突チェック
-        let idx = (y as u32 * field.width + x as u32) as usize;
-        if idx < field.cells.len() && field.cells[idx] != 0 {
-            return true;
-        }
-    }
-    
-    false
+fn get_tetromino_blocks(system: &dyn RotationSystem, kind: TetrominoKind, x: i32, y: i32, rotation: u8) -> Blocks {
+    system.shape(kind, rotation).map(|(dx, dy)| (x + dx, y + dy))
 }
 
-fn get_tetromino_blocks(kind: &str, x: i32, y: i32, rotation: u8) -> Vec<(i32, i32)> {
-    for (k, shapes) in TETROMINO_SHAPES {
-        if *k == kind {
-            let rot = (rotation % 4) as usize;
-            return shapes[rot].iter().map(|(dx, dy)| (x + dx, y + dy)).collect();
-        }
-    }
-    vec![]
+fn tetromino_blocks(system: &dyn RotationSystem, tetromino: &Tetromino) -> Blocks {
+    get_tetromino_blocks(system, tetromino.kind, tetromino.x, tetromino.y, tetromino.rotation)
 }
 
-// fix_tetrominoで4マスすべてをフィールドに埋め込む
-fn fix_tetromino(field: &mut Field, tetromino: &Tetromino) {
-    let blocks = get_tetromino_blocks(&tetromino.kind, tetromino.x, tetromino.y, tetromino.rotation);
-    for (bx, by) in blocks {
-        if bx >= 0 && by >= 0 && (bx as u32) < field.width && (by as u32) < field.height {
-            let idx = (by as u32 * field.width + bx as u32) as usize;
-            if idx < field.cells.len() {
-                field.cells[idx] = 1;
-            }
-        }
-    }
+// fix_tetrominoで4マスすべてをミノの種類・フラグ付きでフィールドに埋め込む
+fn fix_tetromino(system: &dyn RotationSystem, field: &mut BitBoard, tetromino: &Tetromino, flags: u8) {
+    field.place(&tetromino_blocks(system, tetromino), cell::piece_cell(tetromino.kind, flags));
 }
 
-// ライン消去の雛形
-fn clear_lines(field: &mut Field) -> u32 {
-    let width = field.width as usize;
-    let mut new_cells = vec![];
-    let mut cleared = 0;
-    for row in field.cells.chunks(width) {
-        if row.iter().all(|&c| c != 0) {
-            cleared += 1;
-        } else {
-            new_cells.extend_from_slice(row);
-        }
-    }
-    // 上から空行を追加
-    for _ in 0..cleared {
-        new_cells.splice(0..0, vec![0; width]);
-    }
-    field.cells = new_cells;
-    cleared
+// 固定した場合に揃う行数（フィールドは変更しない）
+fn count_completed_lines(system: &dyn RotationSystem, field: &dyn Occupancy, tetromino: &Tetromino) -> u32 {
+    field.completed_rows(&tetromino_blocks(system, tetromino))
 }
 
-fn fix_tetromino_with_tspin(field: &mut Field, tetromino: &Tetromino) {
-    let wallkick = LAST_WALLKICK.with(|w| *w.borrow());
-    let kick_index = LAST_KICK_INDEX.with(|k| *k.borrow());
-    let tspin = detect_t_spin(tetromino, field, wallkick, kick_index);
-    LAST_TSPIN.with(|t| *t.borrow_mut() = tspin);
-    fix_tetromino(field, tetromino);
-    clear_lines(field);
+// 1マス下が埋まっているか（接地判定）
+fn is_grounded(system: &dyn RotationSystem, field: &dyn Occupancy, tetromino: &Tetromino) -> bool {
+    let mut below = tetromino.clone();
+    below.y += 1;
+    check_collision(system, field, &below)
 }
 
-#[wasm_bindgen]
-pub fn move_tetromino(dir: &str) -> JsValue {
-    let mut fixed = false;
-    let mut result = None;
-    FIELD.with(|f| {
-        TETROMINO.with(|t| {
-            let mut tetro = t.borrow().clone();
-            let mut field = f.borrow().clone();
-            match dir {
-                "left" => { tetro.x -= 1; },
-                "right" => { tetro.x += 1; },
-                "down" => { tetro.y += 1; },
-                _ => {}
-            }
-            if check_collision(&field, &tetro) {
-                // 衝突したら元に戻す
-                match dir {
-                    "left" => { tetro.x += 1; },
-                    "right" => { tetro.x -= 1; },
-                    "down" => {
-                        tetro.y -= 1;
-                        // 下方向衝突時は固定
-                        fix_tetromino_with_tspin(&mut field, &tetro);
-                        clear_lines(&mut field);
-                        fixed = true;
-                    },
-                    _ => {}
-                }
-            }
-            *t.borrow_mut() = tetro.clone();
-            *f.borrow_mut() = field.clone();
-            result = Some(serde_wasm_bindgen::to_value(&tetro).unwrap());
-        });
-    });
-    result.unwrap()
-}
-
-fn srs_kick_table() -> KickTable {
-    let mut table = HashMap::new();
-    // 通常ミノ用SRSキックテーブル（例: 0->1, 1->0, ...）
-    table.insert((0, 1), vec![KickOffset {x:0, y:0}, KickOffset {x:-1, y:0}, KickOffset {x:-1, y:1}, KickOffset {x:0, y:-2}, KickOffset {x:-1, y:-2}]);
-    table.insert((1, 0), vec![KickOffset {x:0, y:0}, KickOffset {x:1, y:0}, KickOffset {x:1, y:-1}, KickOffset {x:0, y:2}, KickOffset {x:1, y:2}]);
-    table.insert((1, 2), vec![KickOffset {x:0, y:0}, KickOffset {x:1, y:0}, KickOffset {x:1, y:-1}, KickOffset {x:0, y:2}, KickOffset {x:1, y:2}]);
-    table.insert((2, 1), vec![KickOffset {x:0, y:0}, KickOffset {x:-1, y:0}, KickOffset {x:-1, y:1}, KickOffset {x:0, y:-2}, KickOffset {x:-1, y:-2}]);
-    table.insert((2, 3), vec![KickOffset {x:0, y:0}, KickOffset {x:1, y:0}, KickOffset {x:1, y:1}, KickOffset {x:0, y:-2}, KickOffset {x:1, y:-2}]);
-    table.insert((3, 2), vec![KickOffset {x:0, y:0}, KickOffset {x:-1, y:0}, KickOffset {x:-1, y:-1}, KickOffset {x:0, y:2}, KickOffset {x:-1, y:2}]);
-    table.insert((3, 0), vec![KickOffset {x:0, y:0}, KickOffset {x:-1, y:0}, KickOffset {x:-1, y:-1}, KickOffset {x:0, y:2}, KickOffset {x:-1, y:2}]);
-    table.insert((0, 3), vec![KickOffset {x:0, y:0}, KickOffset {x:1, y:0}, KickOffset {x:1, y:1}, KickOffset {x:0, y:-2}, KickOffset {x:1, y:-2}]);
-    table
-}
-
-fn srs_i_kick_table() -> KickTable {
-    let mut table = HashMap::new();
-    // Iミノ専用SRSキックテーブル
-    table.insert((0, 1), vec![KickOffset {x:0, y:0}, KickOffset {x:-2, y:0}, KickOffset {x:1, y:0}, KickOffset {x:-2, y:-1}, KickOffset {x:1, y:2}]);
-    table.insert((1, 0), vec![KickOffset {x:0, y:0}, KickOffset {x:2, y:0}, KickOffset {x:-1, y:0}, KickOffset {x:2, y:1}, KickOffset {x:-1, y:-2}]);
-    table.insert((1, 2), vec![KickOffset {x:0, y:0}, KickOffset {x:-1, y:0}, KickOffset {x:2, y:0}, KickOffset {x:-1, y:2}, KickOffset {x:2, y:-1}]);
-    table.insert((2, 1), vec![KickOffset {x:0, y:0}, KickOffset {x:1, y:0}, KickOffset {x:-2, y:0}, KickOffset {x:1, y:-2}, KickOffset {x:-2, y:1}]);
-    table.insert((2, 3), vec![KickOffset {x:0, y:0}, KickOffset {x:2, y:0}, KickOffset {x:-1, y:0}, KickOffset {x:2, y:1}, KickOffset {x:-1, y:-2}]);
-    table.insert((3, 2), vec![KickOffset {x:0, y:0}, KickOffset {x:-2, y:0}, KickOffset {x:1, y:0}, KickOffset {x:-2, y:-1}, KickOffset {x:1, y:2}]);
-    table.insert((3, 0), vec![KickOffset {x:0, y:0}, KickOffset {x:1, y:0}, KickOffset {x:-2, y:0}, KickOffset {x:1, y:-2}, KickOffset {x:-2, y:1}]);
-    table.insert((0, 3), vec![KickOffset {x:0, y:0}, KickOffset {x:-1, y:0}, KickOffset {x:2, y:0}, KickOffset {x:-1, y:2}, KickOffset {x:2, y:-1}]);
-    table
+// 全ブロックが可視領域（visible_top段目以降）より上にあるか（ロックアウト判定）
+fn is_lock_out(system: &dyn RotationSystem, tetromino: &Tetromino, visible_top: i32) -> bool {
+    tetromino_blocks(system, tetromino)
+        .iter()
+        .all(|&(_, y)| y < visible_top)
 }
 
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct RotationResult {
     pub tetromino: Tetromino,
@@ -277,26 +117,79 @@ pub struct RotationResult {
     pub kick_index: i32,
 }
 
-// SRS回転処理の返り値を(RotationResult)に
-fn attempt_srs_rotation(tetromino: &Tetromino, field: &Field, clockwise: bool) -> RotationResult {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveDirection {
+    Left,
+    Right,
+    Down,
+}
+
+impl MoveDirection {
+    // "left" | "right" | "down"（それ以外はエラー）
+    pub fn parse(dir: &str) -> Result<Self, EngineError> {
+        match dir {
+            "left" => Ok(MoveDirection::Left),
+            "right" => Ok(MoveDirection::Right),
+            "down" => Ok(MoveDirection::Down),
+            _ => Err(EngineError::InvalidDirection(dir.to_string())),
+        }
+    }
+
+    fn offset(self) -> (i32, i32) {
+        match self {
+            MoveDirection::Left => (-1, 0),
+            MoveDirection::Right => (1, 0),
+            MoveDirection::Down => (0, 1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RotationDirection {
+    Clockwise,
+    CounterClockwise,
+    Half,
+}
+
+impl RotationDirection {
+    // "right" / "clockwise" は時計回り、"left" / "counterclockwise" は反時計回り、"180" は180度
+    pub fn parse(dir: &str) -> Result<Self, EngineError> {
+        match dir {
+            "right" | "clockwise" => Ok(RotationDirection::Clockwise),
+            "left" | "counterclockwise" => Ok(RotationDirection::CounterClockwise),
+            "180" => Ok(RotationDirection::Half),
+            _ => Err(EngineError::InvalidDirection(dir.to_string())),
+        }
+    }
+
+    fn target(self, from: u8) -> u8 {
+        match self {
+            RotationDirection::Clockwise => (from + 1) % 4,
+            RotationDirection::CounterClockwise => (from + 3) % 4,
+            RotationDirection::Half => (from + 2) % 4,
+        }
+    }
+}
+
+// 選択中の回転法則で回転を試み、結果を(RotationResult)に
+fn attempt_rotation(
+    system: &dyn RotationSystem,
+    tetromino: &Tetromino,
+    field: &dyn Occupancy,
+    direction: RotationDirection,
+) -> RotationResult {
     let from = tetromino.rotation;
-    let to = if clockwise {
-        (from + 1) % 4
-    } else {
-        (from + 3) % 4
-    };
-    let kick_table = if tetromino.kind == "I" {
-        srs_i_kick_table()
-    } else {
-        srs_kick_table()
-    };
-    let kicks = kick_table.get(&(from, to)).cloned().unwrap_or_else(|| vec![KickOffset {x:0, y:0}]);
-    for (i, kick) in kicks.iter().enumerate() {
-        let mut test = tetromino.clone();
+    let to = direction.target(from);
+    let mut rotated = tetromino.clone();
+    rotated.rotation = to;
+    for (i, kick) in system.kicks(tetromino.kind, from, to).iter().enumerate() {
+        if i == 1 && !system.allows_kick(&rotated, field) {
+            break;
+        }
+        let mut test = rotated.clone();
         test.x += kick.x;
         test.y += kick.y;
-        test.rotation = to;
-        if !check_collision(field, &test) {
+        if !check_collision(system, field, &test) {
             return RotationResult {
                 tetromino: test,
                 wallkick: i > 0,
@@ -311,106 +204,29 @@ fn attempt_srs_rotation(tetromino: &Tetromino, field: &Field, clockwise: bool) -
     }
 }
 
-#[wasm_bindgen]
-pub fn rotate_tetromino(dir: &str) -> JsValue {
-    let clockwise = dir != "left";
-    let mut result = None;
-    FIELD.with(|f| {
-        TETROMINO.with(|t| {
-            let tetro = t.borrow().clone();
-            let field = f.borrow().clone();
-            let rot_result = attempt_srs_rotation(&tetro, &field, clockwise);
-            
-            // wallkick情報を保存
-            LAST_WALLKICK.with(|w| *w.borrow_mut() = rot_result.wallkick);
-            LAST_KICK_INDEX.with(|k| *k.borrow_mut() = rot_result.kick_index);
-            
-            *t.borrow_mut() = rot_result.tetromino.clone();
-            result = Some(serde_wasm_bindgen::to_value(&rot_result).unwrap());
-        });
-    });
-    result.unwrap()
-}
-
-#[wasm_bindgen]
-pub fn hard_drop() -> JsValue {
-    let mut fixed = false;
-    let mut result = None;
-    FIELD.with(|f| {
-        TETROMINO.with(|t| {
-            let mut tetro = t.borrow().clone();
-            let mut field = f.borrow().clone();
-            // 一番下まで落とす
-            while !check_collision(&field, &tetro) {
-                tetro.y += 1;
-            }
-            tetro.y -= 1;
-            fix_tetromino_with_tspin(&mut field, &tetro);
-            clear_lines(&mut field);
-            fixed = true;
-            *t.borrow_mut() = tetro.clone();
-            *f.borrow_mut() = field.clone();
-            result = Some(serde_wasm_bindgen::to_value(&tetro).unwrap());
-        });
-    });
-    result.unwrap()
-}
-
-#[wasm_bindgen]
-pub fn get_score() -> u32 { 0 }
-
-#[wasm_bindgen]
-pub fn get_rank() -> u8 { 0 }
-
-#[wasm_bindgen]
-pub fn get_achievements() -> JsValue { JsValue::NULL }
-
-#[wasm_bindgen]
-pub fn get_field() -> JsValue {
-    FIELD.with(|f| serde_wasm_bindgen::to_value(&*f.borrow()).unwrap())
-}
-
-#[wasm_bindgen]
-pub fn get_current_tetromino() -> JsValue {
-    TETROMINO.with(|t| serde_wasm_bindgen::to_value(&*t.borrow()).unwrap())
-}
-
-#[wasm_bindgen]
-pub fn get_game_state() -> JsValue {
-    let field = FIELD.with(|f| serde_wasm_bindgen::to_value(&*f.borrow()).unwrap());
-    let tetromino = TETROMINO.with(|t| serde_wasm_bindgen::to_value(&*t.borrow()).unwrap());
-    let last_tspin = LAST_TSPIN.with(|t| serde_wasm_bindgen::to_value(&*t.borrow()).unwrap());
-    let last_wallkick = LAST_WALLKICK.with(|w| serde_wasm_bindgen::to_value(&*w.borrow()).unwrap());
-    let last_kick_index = LAST_KICK_INDEX.with(|k| serde_wasm_bindgen::to_value(&*k.borrow()).unwrap());
-    
-    // JsValueを直接組み立て
-    let obj = js_sys::Object::new();
-    js_sys::Reflect::set(&obj, &"field".into(), &field).unwrap();
-    js_sys::Reflect::set(&obj, &"tetromino".into(), &tetromino).unwrap();
-    js_sys::Reflect::set(&obj, &"lastTspin".into(), &last_tspin).unwrap();
-    js_sys::Reflect::set(&obj, &"lastWallkick".into(), &last_wallkick).unwrap();
-    js_sys::Reflect::set(&obj, &"lastKickIndex".into(), &last_kick_index).unwrap();
-    
-    obj.into()
+// 一番下まで落とした位置（ハードドロップの着地点）
+fn drop_position(system: &dyn RotationSystem, field: &dyn Occupancy, tetromino: &Tetromino) -> Tetromino {
+    let mut landed = tetromino.clone();
+    landed.y += field.drop_distance(&tetromino_blocks(system, tetromino));
+    landed
 }
 
 // Tミノの中心ブロック座標を取得
-fn get_t_center_block(tetromino: &Tetromino) -> (i32, i32) {
-    // Tミノの形状テーブルの1,1が中心
-    (tetromino.x + 1, tetromino.y + 1)
-}
-
-// フィールド上の(x, y)が埋まっているか
-fn is_filled(x: i32, y: i32, field: &Field) -> bool {
-    if x < 0 || y < 0 || (x as u32) >= field.width || (y as u32) >= field.height {
-        return true; // フィールド外は埋まっている扱い（壁・床・天井）
-    }
-    let idx = (y as u32 * field.width + x as u32) as usize;
-    field.cells.get(idx).copied().unwrap_or(0) != 0
+// 回転法則によって形状テーブル上の中心の位置が違うため、他の3ブロックと隣接するブロックを探す
+fn get_t_center_block(system: &dyn RotationSystem, tetromino: &Tetromino) -> (i32, i32) {
+    let blocks = tetromino_blocks(system, tetromino);
+    let neighbours = |(x, y): (i32, i32)| {
+        blocks.iter().filter(|&&(bx, by)| (bx - x).abs() + (by - y).abs() == 1).count()
+    };
+    blocks
+        .iter()
+        .copied()
+        .find(|&block| neighbours(block) == 3)
+        .unwrap_or((tetromino.x + 1, tetromino.y + 1))
 }
 
 // Tミノ中心4角の埋まり数を判定
-fn count_t_spin_corners(center: (i32, i32), field: &Field) -> usize {
+fn count_t_spin_corners(center: (i32, i32), field: &dyn Occupancy) -> usize {
     let (cx, cy) = center;
     let corners = [
         (cx - 1, cy - 1), // 左上
@@ -418,7 +234,33 @@ fn count_t_spin_corners(center: (i32, i32), field: &Field) -> usize {
         (cx - 1, cy + 1), // 左下
         (cx + 1, cy + 1), // 右下
     ];
-    corners.iter().filter(|&&(x, y)| is_filled(x, y, field)).count()
+    corners.iter().filter(|&&(x, y)| field.is_filled(x, y)).count()
+}
+
+// Tミノの向き（中心から突起ブロックへの単位ベクトル）
+// 回転法則ごとに回転番号と向きの対応が違うため形状から求める
+fn t_facing(system: &dyn RotationSystem, tetromino: &Tetromino) -> (i32, i32) {
+    let (cx, cy) = get_t_center_block(system, tetromino);
+    tetromino_blocks(system, tetromino)
+        .iter()
+        .fold((0, 0), |(fx, fy), &(x, y)| (fx + x - cx, fy + y - cy))
+}
+
+// 向いている側の2角（前側）。残りの2角が後ろ側
+fn t_spin_front_corners(center: (i32, i32), facing: (i32, i32)) -> [(i32, i32); 2] {
+    let (cx, cy) = center;
+    let (fx, fy) = facing;
+    // 向きに垂直な方向
+    let (px, py) = (fy, fx);
+    [(cx + fx + px, cy + fy + py), (cx + fx - px, cy + fy - py)]
+}
+
+// SRSの5番目のキック（TSTキック）。90度回転でこれを使った場合は常にT-Spin扱い
+// 180度回転の5番目は(±1,±2)ではなく横へのずらしなので格上げしない
+const TST_KICK_INDEX: i32 = 4;
+
+fn is_tst_kick(rotation: RotationDirection, wallkick: bool, kick_index: i32) -> bool {
+    wallkick && kick_index == TST_KICK_INDEX && rotation != RotationDirection::Half
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -429,49 +271,89 @@ pub enum TSpinType {
 }
 
 // T-Spin判定関数
+// rotation: 最後に成功した操作が回転ならその方向（回転以外で置いた場合はT-Spinなし）
 fn detect_t_spin(
+    system: &dyn RotationSystem,
     tetromino: &Tetromino,
-    field: &Field,
+    field: &dyn Occupancy,
     wallkick: bool,
     kick_index: i32,
+    rotation: Option<RotationDirection>,
 ) -> TSpinType {
-    // Tミノ以外はT-Spinなし
-    if tetromino.kind != "T" {
+    // Tミノ以外・回転で置いていない場合はT-Spinなし
+    let Some(rotation) = rotation else {
+        return TSpinType::None;
+    };
+    if tetromino.kind != TetrominoKind::T {
         return TSpinType::None;
     }
     // 中心座標
-    let center = get_t_center_block(tetromino);
+    let center = get_t_center_block(system, tetromino);
     let corners = count_t_spin_corners(center, field);
-    // 3つ以上角が埋まっていればT-Spin
-    if corners >= 3 {
-        // Mini判定: SRS公式では特定のkick index（0以外）や特定回転でMini
-        // ここではkick_index==0（最初のkick）ならNormal, それ以外はMiniとする
-        if wallkick && kick_index > 0 {
-            TSpinType::Mini
-        } else {
-            TSpinType::Normal
-        }
+    // 3つ以上角が埋まっていなければT-Spinなし
+    if corners < 3 {
+        return TSpinType::None;
+    }
+    // ガイドライン準拠: 前側2角＋後ろ側1角以上ならT-Spin、前側1角＋後ろ側2角ならMini
+    // ただしTSTキックを使った回転はMiniでもT-Spinに格上げ
+    let front = t_spin_front_corners(center, t_facing(system, tetromino));
+    let front_filled = front.iter().filter(|&&(x, y)| field.is_filled(x, y)).count();
+    if front_filled == 2 || is_tst_kick(rotation, wallkick, kick_index) {
+        TSpinType::Normal
     } else {
-        TSpinType::None
+        TSpinType::Mini
     }
 }
 
+// JSから渡された回転方向（省略時は90度回転として扱う）
+fn read_rotation(direction: Option<String>) -> Result<RotationDirection, EngineError> {
+    direction.as_deref().map_or(Ok(RotationDirection::Clockwise), RotationDirection::parse)
+}
+
+// JSから受け取るミノ。種類は文字列のまま読み取り、形式のエラー（InvalidInput）と区別する
+#[derive(Deserialize)]
+struct TetrominoInput {
+    kind: String,
+    x: i32,
+    y: i32,
+    rotation: u8,
+}
+
+impl TryFrom<TetrominoInput> for Tetromino {
+    type Error = EngineError;
+
+    // 未知のミノの種類はUnknownPieceKind
+    fn try_from(input: TetrominoInput) -> Result<Self, Self::Error> {
+        Ok(Tetromino {
+            kind: TetrominoKind::parse(&input.kind)?,
+            x: input.x,
+            y: input.y,
+            rotation: input.rotation,
+        })
+    }
+}
+
+// JSから渡されたミノとフィールドを読み取る
+fn read_piece_and_field(tetromino: JsValue, field: JsValue) -> Result<(Tetromino, Field), EngineError> {
+    let tetromino = Tetromino::try_from(from_js::<TetrominoInput>(tetromino)?)?;
+    let field: Field = from_js(field)?;
+    Ok((tetromino, field))
+}
+
 #[wasm_bindgen]
 pub fn detect_t_spin_wasm(
     tetromino: JsValue,
     field: JsValue,
     wallkick: bool,
     kick_index: i32,
-) -> JsValue {
-    let tetromino: Tetromino = serde_wasm_bindgen::from_value(tetromino).unwrap();
-    let field: Field = serde_wasm_bindgen::from_value(field).unwrap();
-    let result = detect_t_spin(&tetromino, &field, wallkick, kick_index);
-    serde_wasm_bindgen::to_value(&result).unwrap()
-}
-
-#[wasm_bindgen]
-pub fn get_last_tspin_type() -> JsValue {
-    LAST_TSPIN.with(|t| serde_wasm_bindgen::to_value(&*t.borrow()).unwrap())
+    direction: Option<String>,
+) -> Result<JsValue, JsValue> {
+    let (tetromino, field) = read_piece_and_field(tetromino, field)?;
+    let rotation = read_rotation(direction)?;
+    // ミノの形状はデフォルトセッションの回転法則に従う
+    let system = with_session(|s| s.system());
+    let result = detect_t_spin(system, &tetromino, &field, wallkick, kick_index, Some(rotation));
+    to_js(&result)
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -500,189 +382,136 @@ pub struct SpinResult {
     pub lines: u32,
 }
 
-// 各ミノの中心ブロック座標を取得
-fn get_center_block(tetromino: &Tetromino) -> (i32, i32) {
-    match tetromino.kind.as_str() {
-        "T" => (tetromino.x + 1, tetromino.y + 1), // Tミノの形状テーブルの1,1が中心
-        "S" | "Z" => (tetromino.x + 1, tetromino.y + 1), // S/Zミノも同様
-        "I" => (tetromino.x + 1, tetromino.y + 1), // Iミノも同様
-        "J" | "L" => (tetromino.x + 1, tetromino.y + 1), // J/Lミノも同様
-        _ => (tetromino.x, tetromino.y), // その他は基準位置
-    }
+// スピン判定の方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpinRule {
+    // 回転中心の4角のうち3つ以上が埋まっていて、壁蹴りで入った場合にスピン
+    Corner,
+    // 回転直後に左右・上へ動けなければスピン（オールスピン）
+    Immobile,
+    // Immobileと同じ判定で、T以外のスピンはすべてMini扱い
+    ImmobileMini,
 }
 
-// 各ミノの角位置を取得
-fn get_corner_positions(center: (i32, i32), tetromino: &Tetromino) -> [(i32, i32); 4] {
-    let (cx, cy) = center;
-    match tetromino.kind.as_str() {
-        "I" => {
-            // Iミノは長い形状なので、回転に応じて角位置が変わる
-            match tetromino.rotation {
-                0 | 2 => [ // 横長
-                    (cx - 2, cy - 1), // 左上
-                    (cx + 2, cy - 1), // 右上
-                    (cx - 2, cy + 1), // 左下
-                    (cx + 2, cy + 1), // 右下
-                ],
-                1 | 3 => [ // 縦長
-                    (cx - 1, cy - 2), // 左上
-                    (cx + 1, cy - 2), // 右上
-                    (cx - 1, cy + 2), // 左下
-                    (cx + 1, cy + 2), // 右下
-                ],
-                _ => [
-                    (cx - 1, cy - 1), // 左上
-                    (cx + 1, cy - 1), // 右上
-                    (cx - 1, cy + 1), // 左下
-                    (cx + 1, cy + 1), // 右下
-                ],
-            }
-        },
-        _ => {
-            // その他のミノは通常の角位置
-            [
-                (cx - 1, cy - 1), // 左上
-                (cx + 1, cy - 1), // 右上
-                (cx - 1, cy + 1), // 左下
-                (cx + 1, cy + 1), // 右下
-            ]
+impl SpinRule {
+    // JS側から渡される名前を解釈（未知の名前はNone）
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "corner" => Some(SpinRule::Corner),
+            "immobile" | "allspin" => Some(SpinRule::Immobile),
+            "immobile-mini" | "allmini" => Some(SpinRule::ImmobileMini),
+            _ => None,
         }
     }
-}
-
-// 中心4角の埋まり数を判定
-fn count_corners(center: (i32, i32), tetromino: &Tetromino, field: &Field) -> usize {
-    let corners = get_corner_positions(center, tetromino);
-    corners.iter().filter(|&&(x, y)| is_filled(x, y, field)).count()
-}
 
-// S/Z-Spin判定
-fn detect_sz_spin(tetromino: &Tetromino, field: &Field, wallkick: bool, _kick_index: i32) -> SpinResult {
-    if tetromino.kind != "S" && tetromino.kind != "Z" {
-        return SpinResult {
-            spin_type: SpinType::None,
-            variant: SpinVariant::None,
-            bonus: 0,
-            lines: 0,
-        };
-    }
-    
-    if !wallkick {
-        return SpinResult {
-            spin_type: SpinType::None,
-            variant: SpinVariant::None,
-            bonus: 0,
-            lines: 0,
-        };
-    }
-    
-    let center = get_center_block(tetromino);
-    let corners = count_corners(center, tetromino, field);
-    
-    if corners >= 3 {
-        SpinResult {
-            spin_type: SpinType::SZSpin,
-            variant: SpinVariant::Single,
-            bonus: 100,
-            lines: 1,
-        }
-    } else {
-        SpinResult {
-            spin_type: SpinType::None,
-            variant: SpinVariant::None,
-            bonus: 0,
-            lines: 0,
+    pub fn name(self) -> &'static str {
+        match self {
+            SpinRule::Corner => "corner",
+            SpinRule::Immobile => "immobile",
+            SpinRule::ImmobileMini => "immobile-mini",
         }
     }
 }
 
-// I-Spin判定
-fn detect_i_spin(tetromino: &Tetromino, field: &Field, wallkick: bool, _kick_index: i32) -> SpinResult {
-    if tetromino.kind != "I" {
-        return SpinResult {
-            spin_type: SpinType::None,
-            variant: SpinVariant::None,
-            bonus: 0,
-            lines: 0,
-        };
-    }
-    
-    if !wallkick {
-        return SpinResult {
-            spin_type: SpinType::None,
-            variant: SpinVariant::None,
-            bonus: 0,
-            lines: 0,
-        };
-    }
-    
-    let center = get_center_block(tetromino);
-    let corners = count_corners(center, tetromino, field);
-    
-    if corners >= 3 {
-        SpinResult {
-            spin_type: SpinType::ISpin,
-            variant: SpinVariant::Single,
-            bonus: 100,
-            lines: 1,
-        }
-    } else {
-        SpinResult {
-            spin_type: SpinType::None,
-            variant: SpinVariant::None,
-            bonus: 0,
-            lines: 0,
+// スピン判定に使う角の位置
+// J/L/S/Zは3x3枠の中心(1,1)の4角、Iは両端の外側の斜め4マス
+fn get_corner_positions(system: &dyn RotationSystem, tetromino: &Tetromino) -> [(i32, i32); 4] {
+    if tetromino.kind == TetrominoKind::I {
+        let blocks = tetromino_blocks(system, tetromino);
+        let min_x = blocks.iter().map(|&(x, _)| x).min().unwrap_or(tetromino.x);
+        let max_x = blocks.iter().map(|&(x, _)| x).max().unwrap_or(tetromino.x);
+        let min_y = blocks.iter().map(|&(_, y)| y).min().unwrap_or(tetromino.y);
+        let max_y = blocks.iter().map(|&(_, y)| y).max().unwrap_or(tetromino.y);
+        if min_y == max_y {
+            // 横長
+            return [(min_x - 1, min_y - 1), (max_x + 1, min_y - 1), (min_x - 1, min_y + 1), (max_x + 1, min_y + 1)];
         }
+        // 縦長
+        return [(min_x - 1, min_y - 1), (min_x + 1, min_y - 1), (min_x - 1, max_y + 1), (min_x + 1, max_y + 1)];
     }
+    let (dx, dy) = system.corner_center(tetromino.kind, tetromino.rotation);
+    let (cx, cy) = (tetromino.x + dx, tetromino.y + dy);
+    [
+        (cx - 1, cy - 1), // 左上
+        (cx + 1, cy - 1), // 右上
+        (cx - 1, cy + 1), // 左下
+        (cx + 1, cy + 1), // 右下
+    ]
 }
 
-// J/L-Spin判定
-fn detect_jl_spin(tetromino: &Tetromino, field: &Field, wallkick: bool, _kick_index: i32) -> SpinResult {
-    if tetromino.kind != "J" && tetromino.kind != "L" {
-        return SpinResult {
-            spin_type: SpinType::None,
-            variant: SpinVariant::None,
-            bonus: 0,
-            lines: 0,
-        };
-    }
-    
-    if !wallkick {
-        return SpinResult {
-            spin_type: SpinType::None,
-            variant: SpinVariant::None,
-            bonus: 0,
-            lines: 0,
-        };
-    }
-    
-    let center = get_center_block(tetromino);
-    let corners = count_corners(center, tetromino, field);
-    
-    if corners >= 3 {
-        SpinResult {
-            spin_type: SpinType::JLSpin,
-            variant: SpinVariant::Single,
-            bonus: 100,
-            lines: 1,
-        }
-    } else {
-        SpinResult {
-            spin_type: SpinType::None,
-            variant: SpinVariant::None,
-            bonus: 0,
-            lines: 0,
-        }
+// 角の埋まり数を判定
+fn count_corners(system: &dyn RotationSystem, tetromino: &Tetromino, field: &dyn Occupancy) -> usize {
+    let corners = get_corner_positions(system, tetromino);
+    corners.iter().filter(|&&(x, y)| field.is_filled(x, y)).count()
+}
+
+// 左右・上のどこにも動けないか
+fn is_immobile(system: &dyn RotationSystem, field: &dyn Occupancy, tetromino: &Tetromino) -> bool {
+    [(-1, 0), (1, 0), (0, -1)].iter().all(|&(dx, dy)| {
+        let mut moved = tetromino.clone();
+        moved.x += dx;
+        moved.y += dy;
+        check_collision(system, field, &moved)
+    })
+}
+
+fn line_variant(lines: u32) -> SpinVariant {
+    match lines {
+        1 => SpinVariant::Single,
+        2 => SpinVariant::Double,
+        3 => SpinVariant::Triple,
+        _ => SpinVariant::None,
     }
 }
 
-// 統合スピン判定関数
-fn detect_spin(tetromino: &Tetromino, field: &Field, wallkick: bool, kick_index: i32, lines_cleared: u32) -> SpinResult {
-    let result = match tetromino.kind.as_str() {
-        "T" => {
-            let tspin = detect_t_spin(tetromino, field, wallkick, kick_index);
-            match tspin {
-                TSpinType::None => SpinResult { spin_type: SpinType::None, variant: SpinVariant::None, bonus: 0, lines: lines_cleared },
+// T以外のスピンのボーナス点（Miniは半分）
+fn spin_bonus(spin_type: SpinType, lines: u32, mini: bool) -> u32 {
+    let table = match spin_type {
+        SpinType::SZSpin => [800, 2000, 4000],
+        SpinType::ISpin => [600, 1500, 3000],
+        SpinType::JLSpin => [700, 1800, 3500],
+        _ => return 0,
+    };
+    let bonus = match lines {
+        1..=3 => table[lines as usize - 1],
+        _ => 0,
+    };
+    if mini { bonus / 2 } else { bonus }
+}
+
+// 統合スピン判定関数（角判定、rotationの方向に回転して置いたもの）
+fn detect_spin(
+    system: &dyn RotationSystem,
+    tetromino: &Tetromino,
+    field: &dyn Occupancy,
+    wallkick: bool,
+    kick_index: i32,
+    lines_cleared: u32,
+    rotation: RotationDirection,
+) -> SpinResult {
+    let rotation = Some(rotation);
+    detect_spin_with_rule(system, tetromino, field, wallkick, kick_index, lines_cleared, SpinRule::Corner, rotation)
+}
+
+// 判定方式を指定したスピン判定
+// Tミノは方式によらずガイドラインの角判定（T-Spin / Mini）を使う
+// rotation: 最後に成功した操作が回転ならその方向（回転以外で置いた場合はスピンなし）
+#[allow(clippy::too_many_arguments)]
+fn detect_spin_with_rule(
+    system: &dyn RotationSystem,
+    tetromino: &Tetromino,
+    field: &dyn Occupancy,
+    wallkick: bool,
+    kick_index: i32,
+    lines_cleared: u32,
+    rule: SpinRule,
+    rotation: Option<RotationDirection>,
+) -> SpinResult {
+    let no_spin = SpinResult { spin_type: SpinType::None, variant: SpinVariant::None, bonus: 0, lines: lines_cleared };
+    let spin_type = match tetromino.kind {
+        TetrominoKind::T => {
+            return match detect_t_spin(system, tetromino, field, wallkick, kick_index, rotation) {
+                TSpinType::None => no_spin,
                 TSpinType::Mini => {
                     let bonus = match lines_cleared {
                         1 => 1000,
@@ -690,12 +519,6 @@ fn detect_spin(tetromino: &Tetromino, field: &Field, wallkick: bool, kick_index:
                         3 => 3000,
                         _ => 0,
                     };
-                    let _variant = match lines_cleared {
-                        1 => SpinVariant::Single,
-                        2 => SpinVariant::Double,
-                        3 => SpinVariant::Triple,
-                        _ => SpinVariant::None,
-                    };
                     SpinResult { spin_type: SpinType::TSpin, variant: SpinVariant::Mini, bonus, lines: lines_cleared }
                 },
                 TSpinType::Normal => {
@@ -705,71 +528,31 @@ fn detect_spin(tetromino: &Tetromino, field: &Field, wallkick: bool, kick_index:
                         3 => 10000,
                         _ => 0,
                     };
-                    let variant = match lines_cleared {
-                        1 => SpinVariant::Single,
-                        2 => SpinVariant::Double,
-                        3 => SpinVariant::Triple,
-                        _ => SpinVariant::None,
-                    };
-                    SpinResult { spin_type: SpinType::TSpin, variant, bonus, lines: lines_cleared }
+                    SpinResult { spin_type: SpinType::TSpin, variant: line_variant(lines_cleared), bonus, lines: lines_cleared }
                 }
-            }
-        },
-        "S" | "Z" => {
-            let mut result = detect_sz_spin(tetromino, field, wallkick, kick_index);
-            result.lines = lines_cleared;
-            result.bonus = match lines_cleared {
-                1 => 800,
-                2 => 2000,
-                3 => 4000,
-                _ => 0,
-            };
-            result.variant = match lines_cleared {
-                1 => SpinVariant::Single,
-                2 => SpinVariant::Double,
-                3 => SpinVariant::Triple,
-                _ => SpinVariant::None,
-            };
-            result
-        },
-        "I" => {
-            let mut result = detect_i_spin(tetromino, field, wallkick, kick_index);
-            result.lines = lines_cleared;
-            result.bonus = match lines_cleared {
-                1 => 600,
-                2 => 1500,
-                3 => 3000,
-                _ => 0,
-            };
-            result.variant = match lines_cleared {
-                1 => SpinVariant::Single,
-                2 => SpinVariant::Double,
-                3 => SpinVariant::Triple,
-                _ => SpinVariant::None,
             };
-            result
         },
-        "J" | "L" => {
-            let mut result = detect_jl_spin(tetromino, field, wallkick, kick_index);
-            result.lines = lines_cleared;
-            result.bonus = match lines_cleared {
-                1 => 700,
-                2 => 1800,
-                3 => 3500,
-                _ => 0,
-            };
-            result.variant = match lines_cleared {
-                1 => SpinVariant::Single,
-                2 => SpinVariant::Double,
-                3 => SpinVariant::Triple,
-                _ => SpinVariant::None,
-            };
-            result
-        },
-        _ => SpinResult { spin_type: SpinType::None, variant: SpinVariant::None, bonus: 0, lines: lines_cleared },
+        TetrominoKind::S | TetrominoKind::Z => SpinType::SZSpin,
+        TetrominoKind::I => SpinType::ISpin,
+        TetrominoKind::J | TetrominoKind::L => SpinType::JLSpin,
+        TetrominoKind::O => return no_spin,
     };
-    
-    result
+    let rotated = rotation.is_some();
+    let (is_spin, mini) = match rule {
+        // 壁蹴りの記録は次の回転まで残るため、回転した後に移動・落下した場合は除く
+        SpinRule::Corner => (rotated && wallkick && count_corners(system, tetromino, field) >= 3, false),
+        SpinRule::Immobile => (rotated && is_immobile(system, field, tetromino), false),
+        SpinRule::ImmobileMini => (rotated && is_immobile(system, field, tetromino), true),
+    };
+    if !is_spin {
+        return no_spin;
+    }
+    SpinResult {
+        spin_type,
+        variant: if mini { SpinVariant::Mini } else { line_variant(lines_cleared) },
+        bonus: spin_bonus(spin_type, lines_cleared, mini),
+        lines: lines_cleared,
+    }
 }
 
 // WASMバインディング用の統合スピン判定API
@@ -780,17 +563,269 @@ pub fn detect_spin_wasm(
     wallkick: bool,
     kick_index: i32,
     lines_cleared: u32,
-) -> JsValue {
-    let tetromino: Tetromino = serde_wasm_bindgen::from_value(tetromino).unwrap();
-    let field: Field = serde_wasm_bindgen::from_value(field).unwrap();
-    let result = detect_spin(&tetromino, &field, wallkick, kick_index, lines_cleared);
-    serde_wasm_bindgen::to_value(&result).unwrap()
+    direction: Option<String>,
+) -> Result<JsValue, JsValue> {
+    let (tetromino, field) = read_piece_and_field(tetromino, field)?;
+    let rotation = read_rotation(direction)?;
+    let system = with_session(|s| s.system());
+    let result = detect_spin(system, &tetromino, &field, wallkick, kick_index, lines_cleared, rotation);
+    to_js(&result)
+}
+
+
+// 以下はデフォルトセッションに対する従来のAPI（GameSessionの同名メソッドと同じ動作）
+
+// seed省略時はランダムなシードを採番する（get_seedで後から取得して再現可能）
+// randomizer: "7bag" | "14bag" | "tgm" | "random"（省略時は7bag）
+// rotation: "srs" | "ars" | "nrs"（省略時はsrs）
+// height: 可視段数。buffer_rows: その上の非表示の段数（省略時は20）
+// ミノの座標はバッファを含めたフィールド全体の座標（可視部分の行 = y - bufferRows）
+#[wasm_bindgen]
+pub fn init_field(
+    width: u32,
+    height: u32,
+    seed: Option<u32>,
+    randomizer: Option<String>,
+    rotation: Option<String>,
+    buffer_rows: Option<u32>,
+) -> Result<JsValue, JsValue> {
+    with_session(|s| s.init_field(width, height, seed, randomizer, rotation, buffer_rows))
+}
+
+#[wasm_bindgen]
+pub fn get_rotation_system() -> String {
+    with_session(|s| s.get_rotation_system())
+}
+
+// マスの値からミノの種類を取得（"I"〜"L"、おじゃまブロックは"G"、空はundefined）
+#[wasm_bindgen]
+pub fn get_cell_kind(cell: u8) -> Option<String> {
+    cell::cell_kind(cell).map(str::to_string)
+}
+
+// ホールドから出したミノで置かれたマスか
+#[wasm_bindgen]
+pub fn is_cell_from_hold(cell: u8) -> bool {
+    cell::has_flag(cell, cell::FLAG_FROM_HOLD)
+}
+
+// スピン判定の方式: "corner" | "immobile" | "immobile-mini"（未知の名前はエラー）
+#[wasm_bindgen]
+pub fn set_spin_rule(rule: &str) -> Result<(), JsValue> {
+    with_session(|s| s.set_spin_rule(rule))
+}
+
+#[wasm_bindgen]
+pub fn get_spin_rule() -> String {
+    with_session(|s| s.get_spin_rule())
+}
+
+#[wasm_bindgen]
+pub fn is_game_over() -> bool {
+    with_session(|s| s.is_game_over())
+}
+
+#[wasm_bindgen]
+pub fn spawn_tetromino() -> Result<JsValue, JsValue> {
+    with_session(|s| s.spawn_tetromino())
+}
+
+#[wasm_bindgen]
+pub fn hold_tetromino() -> Result<JsValue, JsValue> {
+    with_session(|s| s.hold_tetromino())
+}
+
+// ホールド1回のコストをポイント台帳から支払わせる（省略時は15、フィーバー中は無料、0でコストなし）
+#[wasm_bindgen]
+pub fn set_hold_cost(cost: Option<u32>) {
+    with_session(|s| s.set_hold_cost(cost));
+}
+
+#[wasm_bindgen]
+pub fn exchange_piece() -> Result<JsValue, JsValue> {
+    with_session(|s| s.exchange_piece())
+}
+
+#[wasm_bindgen]
+pub fn emergency_delete_line() -> Result<JsValue, JsValue> {
+    with_session(|s| s.emergency_delete_line())
+}
+
+// 実績解除の報酬ポイントを加算する
+#[wasm_bindgen]
+pub fn add_achievement_points(amount: u32) -> Result<JsValue, JsValue> {
+    with_session(|s| s.add_achievement_points(amount))
+}
+
+#[wasm_bindgen]
+pub fn get_points() -> u32 {
+    with_session(|s| s.get_points())
+}
+
+// 次のエクスチェンジのコスト（フィーバー中は0）
+#[wasm_bindgen]
+pub fn get_exchange_cost() -> u32 {
+    with_session(|s| s.get_exchange_cost())
+}
+
+// このゲームのポイント取引履歴
+#[wasm_bindgen]
+pub fn get_points_history() -> Result<JsValue, JsValue> {
+    with_session(|s| s.get_points_history())
+}
+
+#[wasm_bindgen]
+pub fn move_tetromino(dir: &str) -> Result<JsValue, JsValue> {
+    with_session(|s| s.move_tetromino(dir))
+}
+
+// 経過時間(ms)だけエンジンの時間を進める（フィーバー・自然落下・固定猶予）
+#[wasm_bindgen]
+pub fn tick(dt_ms: u32) -> Result<JsValue, JsValue> {
+    with_session(|s| s.tick(dt_ms))
+}
+
+// 開始レベルを設定（次のinit_fieldから適用、現在のゲームにも即時反映）
+#[wasm_bindgen]
+pub fn set_level(level: u32) {
+    with_session(|s| s.set_level(level));
+}
+
+#[wasm_bindgen]
+pub fn get_level() -> u32 {
+    with_session(|s| s.get_level())
+}
+
+#[wasm_bindgen]
+pub fn get_lines_cleared() -> u32 {
+    with_session(|s| s.get_lines_cleared())
+}
+
+// 固定猶予の設定（ガイドライン標準は500ms・15回）
+#[wasm_bindgen]
+pub fn set_lock_delay(delay_ms: u32, max_resets: u32) {
+    with_session(|s| s.set_lock_delay(delay_ms, max_resets));
+}
+
+// dir: "right"（時計回り） | "left"（反時計回り） | "180"
+#[wasm_bindgen]
+pub fn rotate_tetromino(dir: &str) -> Result<JsValue, JsValue> {
+    with_session(|s| s.rotate_tetromino(dir))
+}
+
+// ゴースト（着地予測）の位置。hard_dropと同じ計算なので必ず一致する
+#[wasm_bindgen]
+pub fn get_ghost_position() -> Result<JsValue, JsValue> {
+    with_session(|s| s.get_ghost_position())
+}
+
+#[wasm_bindgen]
+pub fn hard_drop() -> Result<JsValue, JsValue> {
+    with_session(|s| s.hard_drop())
+}
+
+// NEXTに表示するミノ（先頭が次に出るミノ）
+#[wasm_bindgen]
+pub fn get_next_queue() -> Result<JsValue, JsValue> {
+    with_session(|s| s.get_next_queue())
+}
+
+// NEXTの表示数を設定（1〜7に丸める）
+#[wasm_bindgen]
+pub fn set_next_queue_size(size: u32) {
+    with_session(|s| s.set_next_queue_size(size));
+}
+
+// ゲームオーバーの理由（プレイ中はnull）: "BlockOut" | "LockOut"
+#[wasm_bindgen]
+pub fn get_game_over_reason() -> Result<JsValue, JsValue> {
+    with_session(|s| s.get_game_over_reason())
+}
+
+#[wasm_bindgen]
+pub fn get_seed() -> u32 {
+    with_session(|s| s.get_seed())
+}
+
+#[wasm_bindgen]
+pub fn get_score() -> u32 {
+    with_session(|s| s.get_score())
+}
+
+// 実績判定用の統計（AchievementManager.check_achievementsにそのまま渡せる）
+#[wasm_bindgen]
+pub fn get_game_stats() -> Result<JsValue, JsValue> {
+    with_session(|s| s.get_game_stats())
+}
+
+// 直前の固定で得たスコアの内訳（まだ固定していなければnull）
+#[wasm_bindgen]
+pub fn get_last_score_award() -> Result<JsValue, JsValue> {
+    with_session(|s| s.get_last_score_award())
+}
+
+#[wasm_bindgen]
+pub fn get_rank() -> u8 {
+    with_session(|s| s.get_rank())
+}
+
+#[wasm_bindgen]
+pub fn get_fever_state() -> Result<JsValue, JsValue> {
+    with_session(|s| s.get_fever_state())
+}
+
+// 段位の名前・色・閾値・次の段位までの進捗
+#[wasm_bindgen]
+pub fn get_rank_info() -> Result<JsValue, JsValue> {
+    with_session(|s| s.get_rank_info())
+}
+
+#[wasm_bindgen]
+pub fn get_achievements() -> JsValue { JsValue::NULL }
+
+// 可視部分のフィールド
+#[wasm_bindgen]
+pub fn get_field() -> Result<JsValue, JsValue> {
+    with_session(|s| s.get_field())
+}
+
+// バッファを含めたフィールド全体
+#[wasm_bindgen]
+pub fn get_full_field() -> Result<JsValue, JsValue> {
+    with_session(|s| s.get_full_field())
+}
+
+#[wasm_bindgen]
+pub fn get_buffer_rows() -> u32 {
+    with_session(|s| s.get_buffer_rows())
+}
+
+#[wasm_bindgen]
+pub fn get_current_tetromino() -> Result<JsValue, JsValue> {
+    with_session(|s| s.get_current_tetromino())
+}
+
+#[wasm_bindgen]
+pub fn get_last_tspin_type() -> Result<JsValue, JsValue> {
+    with_session(|s| s.get_last_tspin_type())
+}
+
+#[wasm_bindgen]
+pub fn get_game_state() -> Result<JsValue, JsValue> {
+    with_session(|s| s.get_game_state())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn srs() -> &'static dyn RotationSystem {
+        RotationSystemKind::Srs.system()
+    }
+
+    // 時計回りに回転して置いた
+    const CW: Option<RotationDirection> = Some(RotationDirection::Clockwise);
+
     #[test]
     fn test_t_spin_detection() {
         // 空のフィールド
@@ -802,7 +837,7 @@ mod tests {
 
         // Tミノ（0度回転）
         let t_tetromino = Tetromino {
-            kind: "T".to_string(),
+            kind: TetrominoKind::T,
             x: 4,
             y: 18, // 下の方に配置
             rotation: 0,
@@ -818,40 +853,46 @@ mod tests {
         field_with_walls.cells[18 * 10 + 4] = 1; // 左 (4,18)
         field_with_walls.cells[18 * 10 + 6] = 1; // 右 (6,18)
 
-        let result = detect_t_spin(&t_tetromino, &field_with_walls, false, 0);
+        let result = detect_t_spin(srs(), &t_tetromino, &field_with_walls, false, 0, CW);
         assert_eq!(result, TSpinType::Normal);
 
-        // wallkickありのT-Spin Mini
-        let result = detect_t_spin(&t_tetromino, &field_with_walls, true, 1);
-        assert_eq!(result, TSpinType::Mini);
+        // 前側2角が埋まっていれば壁蹴り後でもT-Spin（Miniにしない）
+        let result = detect_t_spin(srs(), &t_tetromino, &field_with_walls, true, 1, CW);
+        assert_eq!(result, TSpinType::Normal);
 
         // Tミノ以外はT-Spinなし
         let i_tetromino = Tetromino {
-            kind: "I".to_string(),
+            kind: TetrominoKind::I,
             x: 4,
             y: 18,
             rotation: 0,
         };
-        let result = detect_t_spin(&i_tetromino, &field_with_walls, false, 0);
+        let result = detect_t_spin(srs(), &i_tetromino, &field_with_walls, false, 0, CW);
         assert_eq!(result, TSpinType::None);
 
         // 角が2つ以下ならT-Spinなし
         let mut field_partial = field.clone();
         field_partial.cells[19 * 10 + 5] = 1; // 下のみ（中心の下）
-        let result = detect_t_spin(&t_tetromino, &field_partial, false, 0);
+        let result = detect_t_spin(srs(), &t_tetromino, &field_partial, false, 0, CW);
         assert_eq!(result, TSpinType::None);
     }
 
     #[test]
     fn test_t_center_block() {
         let t_tetromino = Tetromino {
-            kind: "T".to_string(),
+            kind: TetrominoKind::T,
             x: 4,
             y: 18,
             rotation: 0,
         };
-        let center = get_t_center_block(&t_tetromino);
+        let center = get_t_center_block(srs(), &t_tetromino);
         assert_eq!(center, (5, 19)); // x+1, y+1
+
+        // ARSの下向き（回転2）は中心が1段下
+        let ars = RotationSystemKind::Ars.system();
+        let t_tetromino = Tetromino { rotation: 2, ..t_tetromino };
+        assert_eq!(get_t_center_block(ars, &t_tetromino), (5, 20));
+        assert_eq!(t_facing(ars, &t_tetromino), (0, -1));
     }
 
     #[test]
@@ -862,12 +903,12 @@ mod tests {
             cells: vec![0; 200],
         };
         let tetromino = Tetromino {
-            kind: "T".to_string(),
+            kind: TetrominoKind::T,
             x: 4,
             y: 4,
             rotation: 0,
         };
-        let center = get_t_center_block(&tetromino);
+        let center = get_t_center_block(srs(), &tetromino);
         let corners = count_t_spin_corners(center, &field);
         assert_eq!(corners, 0);
     }
@@ -880,12 +921,12 @@ mod tests {
             cells: vec![0; 200],
         };
         let tetromino = Tetromino {
-            kind: "T".to_string(),
+            kind: TetrominoKind::T,
             x: 4,
             y: 4,
             rotation: 0,
         };
-        let result = attempt_srs_rotation(&tetromino, &field, true);
+        let result = attempt_rotation(srs(), &tetromino, &field, RotationDirection::Clockwise);
         assert_eq!(result.tetromino.rotation, 1);
         assert!(!result.wallkick); // 通常回転なのでwallkickなし
     }
@@ -902,12 +943,12 @@ mod tests {
             field.cells[y * 10 + 9] = 1;
         }
         let tetromino = Tetromino {
-            kind: "T".to_string(),
+            kind: TetrominoKind::T,
             x: 7,
             y: 4,
             rotation: 0,
         };
-        let result = attempt_srs_rotation(&tetromino, &field, true);
+        let result = attempt_rotation(srs(), &tetromino, &field, RotationDirection::Clockwise);
         assert_eq!(result.tetromino.rotation, 1);
         assert!(result.wallkick); // 壁蹴りが必要
         assert!(result.kick_index > 0);
@@ -929,13 +970,13 @@ mod tests {
         
         // 2. 左右の壁を埋める
         for y in 0..21 {
-            field.cells[y * 10 + 0] = 1; // 左端を埋める
+            field.cells[y * 10] = 1; // 左端を埋める
             field.cells[y * 10 + 9] = 1; // 右端を埋める
         }
         
         // 3. Tミノを90度回転済みで配置（中心が(5,19)）
         let tetromino = Tetromino {
-            kind: "T".to_string(),
+            kind: TetrominoKind::T,
             x: 0, // 左壁に隣接（中心が(1,19)）
             y: 18,
             rotation: 1, // 90度回転済み
@@ -957,23 +998,23 @@ mod tests {
         
         // 6. 各角の埋まり状況を確認
         for (i, &(x, y)) in corners.iter().enumerate() {
-            let filled = is_filled(x, y, &field);
+            let filled = field.is_filled(x, y);
             println!("corner {}: ({}, {}) -> filled: {} (field: {}x{})", i, x, y, filled, field.width, field.height);
         }
         
         // フィールド外のテスト
         println!("field boundary test:");
-        println!("(-1, 0) -> filled: {}", is_filled(-1, 0, &field));
-        println!("(10, 0) -> filled: {}", is_filled(10, 0, &field));
-        println!("(0, -1) -> filled: {}", is_filled(0, -1, &field));
-        println!("(0, 21) -> filled: {}", is_filled(0, 21, &field));
+        println!("(-1, 0) -> filled: {}", field.is_filled(-1, 0));
+        println!("(10, 0) -> filled: {}", field.is_filled(10, 0));
+        println!("(0, -1) -> filled: {}", field.is_filled(0, -1));
+        println!("(0, 21) -> filled: {}", field.is_filled(0, 21));
         
         // 7. スピン判定を実行（wallkick=true, kick_index=1でT-Spin Mini）
-        let spin_result = detect_spin(&tetromino, &field, true, 1, 1);
+        let spin_result = detect_spin(srs(), &tetromino, &field, true, 1, 1, RotationDirection::Clockwise);
         println!("spin_result: {:?}", spin_result);
         
         // T-Spin判定の詳細を確認
-        let center = get_t_center_block(&tetromino);
+        let center = get_t_center_block(srs(), &tetromino);
         let corner_count = count_t_spin_corners(center, &field);
         println!("T-Spin corner count: {} (center: {:?})", corner_count, center);
         
@@ -991,19 +1032,302 @@ mod tests {
         };
         // 衝突しない位置（フィールド中央は空）
         let tetromino = Tetromino {
-            kind: "T".to_string(),
+            kind: TetrominoKind::T,
             x: 4,
             y: 9,
             rotation: 0,
         };
-        let blocks = get_tetromino_blocks(&tetromino.kind, tetromino.x, tetromino.y, tetromino.rotation);
+        let blocks = get_tetromino_blocks(srs(), tetromino.kind, tetromino.x, tetromino.y, tetromino.rotation);
         println!("tetromino blocks: {:?}", blocks);
-        assert!(!check_collision(&field, &tetromino));
+        assert!(!check_collision(srs(), &field, &tetromino));
         // 衝突する位置（中央にブロックを配置）
         field.cells[10 * 10 + 5] = 1;
-        let mut colliding_tetromino = tetromino.clone();
-        let blocks2 = get_tetromino_blocks(&colliding_tetromino.kind, colliding_tetromino.x, colliding_tetromino.y, colliding_tetromino.rotation);
+        let colliding_tetromino = tetromino.clone();
+        let blocks2 = get_tetromino_blocks(srs(), colliding_tetromino.kind, colliding_tetromino.x, colliding_tetromino.y, colliding_tetromino.rotation);
         println!("colliding blocks: {:?}", blocks2);
-        assert!(check_collision(&field, &colliding_tetromino));
+        assert!(check_collision(srs(), &field, &colliding_tetromino));
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_count_completed_lines_before_fix() {
+        let mut field = Field { width: 10, height: 20, cells: vec![0; 200] };
+        for y in 18..20 {
+            for x in 0..10 {
+                if x != 4 {
+                    field.cells[y * 10 + x] = 1;
+                }
+            }
+        }
+        let vertical_i = Tetromino { kind: TetrominoKind::I, x: 2, y: 16, rotation: 1 };
+        assert_eq!(count_completed_lines(srs(), &field, &vertical_i), 2);
+        assert!(field.cells[18 * 10 + 4] == 0);
+    }
+
+    #[test]
+    fn test_180_rotation() {
+        let field = Field { width: 10, height: 20, cells: vec![0; 200] };
+        let tetromino = Tetromino { kind: TetrominoKind::T, x: 4, y: 4, rotation: 0 };
+        let result = attempt_rotation(srs(), &tetromino, &field, RotationDirection::Half);
+        assert_eq!(result.tetromino.rotation, 2);
+        assert_eq!(result.kick_index, 0);
+        assert!(!result.wallkick);
+
+        let i_piece = Tetromino { kind: TetrominoKind::I, x: 3, y: 4, rotation: 1 };
+        let result = attempt_rotation(srs(), &i_piece, &field, RotationDirection::Half);
+        assert_eq!(result.tetromino.rotation, 3);
+    }
+
+    #[test]
+    fn test_180_rotation_uses_kick_table() {
+        // Tミノ(0度)の真下のマスを塞ぎ、180度回転を横方向の壁蹴り(5番目のオフセット)で通す
+        let mut field = Field { width: 10, height: 20, cells: vec![0; 200] };
+        field.cells[6 * 10 + 5] = 1;
+        let tetromino = Tetromino { kind: TetrominoKind::T, x: 4, y: 4, rotation: 0 };
+        let result = attempt_rotation(srs(), &tetromino, &field, RotationDirection::Half);
+        assert_eq!(result.tetromino.rotation, 2);
+        assert!(result.wallkick);
+        assert_eq!(result.kick_index, 4);
+        assert!(!check_collision(srs(), &field, &result.tetromino));
+
+        // 180度回転後のT-Spin判定
+        let mut spin_field = Field { width: 10, height: 20, cells: vec![0; 200] };
+        for &(x, y) in &[(4, 18), (6, 18), (4, 20), (6, 20)] {
+            if y < 20 {
+                spin_field.cells[y * 10 + x] = 1;
+            }
+        }
+        let t = Tetromino { kind: TetrominoKind::T, x: 4, y: 18, rotation: 2 };
+        assert_eq!(detect_t_spin(srs(), &t, &spin_field, false, 0, CW), TSpinType::Normal);
+    }
+
+    #[test]
+    fn test_rotation_direction_parse() {
+        assert_eq!(RotationDirection::parse("left"), Ok(RotationDirection::CounterClockwise));
+        assert_eq!(RotationDirection::parse("right"), Ok(RotationDirection::Clockwise));
+        assert_eq!(RotationDirection::parse("clockwise"), Ok(RotationDirection::Clockwise));
+        assert_eq!(RotationDirection::parse("180"), Ok(RotationDirection::Half));
+        assert_eq!(RotationDirection::parse("up"), Err(EngineError::InvalidDirection("up".to_string())));
+        assert_eq!(MoveDirection::parse("down"), Ok(MoveDirection::Down));
+        assert!(MoveDirection::parse("up").is_err());
+    }
+
+    #[test]
+    fn test_tetromino_kind_is_checked_on_deserialize() {
+        let t: Tetromino = serde_json::from_str(r#"{"kind":"T","x":4,"y":8,"rotation":0}"#).unwrap();
+        assert_eq!(t.kind, TetrominoKind::T);
+        assert_eq!(serde_json::to_string(&t).unwrap(), r#"{"kind":"T","x":4,"y":8,"rotation":0}"#);
+        assert!(serde_json::from_str::<Tetromino>(r#"{"kind":"X","x":4,"y":8,"rotation":0}"#).is_err());
+    }
+
+    #[test]
+    fn test_unknown_piece_kind_from_js_is_reported_as_such() {
+        // read_piece_and_fieldと同じく文字列のまま読み取ってから種類を判定する
+        let read = |json: &str| {
+            let input: TetrominoInput = serde_json::from_str(json).unwrap();
+            Tetromino::try_from(input)
+        };
+        let t = read(r#"{"kind":"T","x":4,"y":8,"rotation":1}"#).unwrap();
+        assert_eq!((t.kind, t.x, t.y, t.rotation), (TetrominoKind::T, 4, 8, 1));
+        let err = read(r#"{"kind":"t","x":4,"y":8,"rotation":0}"#).err();
+        assert_eq!(err, Some(EngineError::UnknownPieceKind("t".to_string())));
+        // 形式の違うオブジェクトは読み取り自体に失敗する（InvalidInput）
+        assert!(serde_json::from_str::<TetrominoInput>(r#"{"kind":"T","x":4}"#).is_err());
+    }
+
+    #[test]
+    fn test_t_spin_mini_front_back_corners_all_orientations() {
+        // Tミノ(4,8)の中心は(5,9)（ARSの回転2のみ(5,10)）。回転ごとの前側2角・後ろ側2角
+        let ars = RotationSystemKind::Ars.system();
+        let cases = [
+            (srs(), 0, [(4, 8), (6, 8)], [(4, 10), (6, 10)]),   // 上向き
+            (srs(), 1, [(6, 8), (6, 10)], [(4, 8), (4, 10)]),   // 右向き
+            (srs(), 2, [(4, 10), (6, 10)], [(4, 8), (6, 8)]),   // 下向き
+            (srs(), 3, [(4, 8), (4, 10)], [(6, 8), (6, 10)]),   // 左向き
+            (ars, 0, [(4, 10), (6, 10)], [(4, 8), (6, 8)]),     // 下向き
+            (ars, 1, [(4, 8), (4, 10)], [(6, 8), (6, 10)]),     // 左向き
+            (ars, 2, [(4, 9), (6, 9)], [(4, 11), (6, 11)]),     // 上向き
+            (ars, 3, [(6, 8), (6, 10)], [(4, 8), (4, 10)]),     // 右向き
+        ];
+        for (system, rotation, front, back) in cases {
+            let t = Tetromino { kind: TetrominoKind::T, x: 4, y: 8, rotation };
+            let field_with = |cells: &[(usize, usize)]| {
+                let mut field = Field { width: 10, height: 20, cells: vec![0; 200] };
+                for &(x, y) in cells {
+                    field.cells[y * 10 + x] = 1;
+                }
+                field
+            };
+
+            // 前側2角＋後ろ側1角 → T-Spin
+            let field = field_with(&[front[0], front[1], back[0]]);
+            assert_eq!(detect_t_spin(system, &t, &field, false, 0, CW), TSpinType::Normal, "rotation {}", rotation);
+            assert_eq!(detect_t_spin(system, &t, &field, true, 2, CW), TSpinType::Normal, "rotation {}", rotation);
+            // 回転以外の操作で置いた場合はT-Spinなし
+            assert_eq!(detect_t_spin(system, &t, &field, false, 0, None), TSpinType::None, "rotation {}", rotation);
+
+            // 前側1角＋後ろ側2角 → Mini
+            let field = field_with(&[front[0], back[0], back[1]]);
+            assert_eq!(detect_t_spin(system, &t, &field, false, 0, CW), TSpinType::Mini, "rotation {}", rotation);
+            assert_eq!(detect_t_spin(system, &t, &field, true, 1, CW), TSpinType::Mini, "rotation {}", rotation);
+            // TSTキック（5番目）ならT-Spinに格上げ
+            assert_eq!(detect_t_spin(system, &t, &field, true, TST_KICK_INDEX, CW), TSpinType::Normal, "rotation {}", rotation);
+            // 180度回転の5番目のキックは横へのずらしなので格上げしない
+            let half = Some(RotationDirection::Half);
+            assert_eq!(detect_t_spin(system, &t, &field, true, TST_KICK_INDEX, half), TSpinType::Mini, "rotation {}", rotation);
+
+            // 後ろ側2角のみ → なし
+            let field = field_with(&[back[0], back[1]]);
+            assert_eq!(detect_t_spin(system, &t, &field, false, 0, CW), TSpinType::None, "rotation {}", rotation);
+        }
+    }
+
+    #[test]
+    fn test_corner_spin_uses_real_i_corners_and_line_count() {
+        let mut field = Field { width: 10, height: 20, cells: vec![0; 200] };
+        // 横長のIミノ(2..=5, 19)の両端外側の斜め: (1,18),(6,18),(1,20),(6,20)
+        field.cells[18 * 10 + 1] = 1;
+        let i_piece = Tetromino { kind: TetrominoKind::I, x: 2, y: 18, rotation: 0 };
+        assert_eq!(get_corner_positions(srs(), &i_piece), [(1, 18), (6, 18), (1, 20), (6, 20)]);
+        let spin = detect_spin(srs(), &i_piece, &field, true, 1, 2, RotationDirection::Clockwise);
+        assert_eq!(spin.spin_type, SpinType::ISpin);
+        assert_eq!(spin.variant, SpinVariant::Double);
+        assert_eq!(spin.lines, 2);
+        assert_eq!(spin.bonus, 1500);
+        // 壁蹴りなしはスピンにならない
+        let spin = detect_spin(srs(), &i_piece, &field, false, 0, 1, RotationDirection::Clockwise);
+        assert_eq!(spin.spin_type, SpinType::None);
+        assert_eq!(spin.variant, SpinVariant::None);
+        assert_eq!(spin.bonus, 0);
+        // 壁蹴りで回転した後に移動・落下して置いた場合もスピンにならない
+        let spin = detect_spin_with_rule(srs(), &i_piece, &field, true, 1, 2, SpinRule::Corner, None);
+        assert_eq!(spin.spin_type, SpinType::None);
+        assert_eq!(spin.bonus, 0);
+    }
+
+    #[test]
+    fn test_corner_spin_uses_rotation_system_center() {
+        let field_with = |cells: &[(usize, usize)]| {
+            let mut field = Field { width: 10, height: 20, cells: vec![0; 200] };
+            for &(x, y) in cells {
+                field.cells[y * 10 + x] = 1;
+            }
+            field
+        };
+
+        // ARSの下向きのJ(3,16)は中心(4,18)。左上の角はミノ自身
+        let ars = RotationSystemKind::Ars.system();
+        let j_piece = Tetromino { kind: TetrominoKind::J, x: 3, y: 16, rotation: 2 };
+        assert_eq!(get_corner_positions(ars, &j_piece), [(3, 17), (5, 17), (3, 19), (5, 19)]);
+        let field = field_with(&[(5, 17), (3, 19), (5, 19)]);
+        let spin = detect_spin_with_rule(ars, &j_piece, &field, true, 1, 0, SpinRule::Corner, CW);
+        assert_eq!(spin.spin_type, SpinType::JLSpin);
+
+        // NRSの横向きのS(3,16)は中心(4,18)。右上の角はミノ自身
+        let nrs = RotationSystemKind::Nrs.system();
+        let s_piece = Tetromino { kind: TetrominoKind::S, x: 3, y: 16, rotation: 0 };
+        assert_eq!(get_corner_positions(nrs, &s_piece), [(3, 17), (5, 17), (3, 19), (5, 19)]);
+        let field = field_with(&[(3, 17), (3, 19), (5, 19)]);
+        assert_eq!(count_corners(nrs, &s_piece, &field), 3);
+        let spin = detect_spin_with_rule(nrs, &s_piece, &field, true, 1, 0, SpinRule::Corner, CW);
+        assert_eq!(spin.spin_type, SpinType::SZSpin);
+    }
+
+    #[test]
+    fn test_immobile_spin_rule() {
+        // Sミノを左右・上を塞いだ穴に入れる
+        let mut field = Field { width: 10, height: 20, cells: vec![0; 200] };
+        let s_piece = Tetromino { kind: TetrominoKind::S, x: 3, y: 17, rotation: 0 };
+        let blocks = get_tetromino_blocks(srs(), TetrominoKind::S, 3, 17, 0);
+        for y in 15..20 {
+            for x in 0..10 {
+                if !blocks.contains(&(x, y)) {
+                    field.cells[(y * 10 + x) as usize] = 1;
+                }
+            }
+        }
+        assert!(is_immobile(srs(), &field, &s_piece));
+        let spin = detect_spin_with_rule(srs(), &s_piece, &field, false, 0, 1, SpinRule::Immobile, CW);
+        assert_eq!(spin.spin_type, SpinType::SZSpin);
+        assert_eq!(spin.variant, SpinVariant::Single);
+        assert_eq!(spin.bonus, 800);
+
+        let spin = detect_spin_with_rule(srs(), &s_piece, &field, false, 0, 1, SpinRule::ImmobileMini, CW);
+        assert_eq!(spin.variant, SpinVariant::Mini);
+        assert_eq!(spin.bonus, 400);
+
+        // 最後の操作が回転でなければスピンにならない
+        let spin = detect_spin_with_rule(srs(), &s_piece, &field, false, 0, 1, SpinRule::Immobile, None);
+        assert_eq!(spin.spin_type, SpinType::None);
+
+        // 上が空いていれば動けるのでスピンにならない
+        let mut open = field.clone();
+        for y in 15..18 {
+            for x in 0..10 {
+                open.cells[y * 10 + x] = 0;
+            }
+        }
+        assert!(!is_immobile(srs(), &open, &s_piece));
+        let spin = detect_spin_with_rule(srs(), &s_piece, &open, false, 0, 1, SpinRule::Immobile, CW);
+        assert_eq!(spin.spin_type, SpinType::None);
+    }
+
+    #[test]
+    fn test_lock_out_detection() {
+        let above = Tetromino { kind: TetrominoKind::I, x: 3, y: -2, rotation: 0 };
+        assert!(is_lock_out(srs(), &above, 0));
+        let partly_visible = Tetromino { kind: TetrominoKind::I, x: 3, y: -2, rotation: 1 };
+        assert!(!is_lock_out(srs(), &partly_visible, 0));
+
+        // バッファ20段なら可視領域のすぐ上で固定したらロックアウト
+        let above = Tetromino { kind: TetrominoKind::I, x: 3, y: 18, rotation: 0 };
+        assert!(is_lock_out(srs(), &above, 20));
+        let reaching = Tetromino { kind: TetrominoKind::I, x: 3, y: 18, rotation: 1 };
+        assert!(!is_lock_out(srs(), &reaching, 20));
+    }
+
+    #[test]
+    fn test_rotation_system_selects_shapes_and_kicks() {
+        // クラシックでは壁蹴りしない
+        let nrs = RotationSystemKind::Nrs.system();
+        let mut field = Field { width: 10, height: 20, cells: vec![0; 200] };
+        let t = Tetromino { kind: TetrominoKind::T, x: -1, y: 4, rotation: 1 };
+        assert!(!check_collision(nrs, &field, &t));
+        let result = attempt_rotation(nrs, &t, &field, RotationDirection::Clockwise);
+        assert_eq!(result.kick_index, -1);
+
+        // ARSでは右へ1マスずれて回転できる
+        let ars = RotationSystemKind::Ars.system();
+        let t = Tetromino { kind: TetrominoKind::T, x: -1, y: 4, rotation: 3 };
+        assert!(!check_collision(ars, &field, &t));
+        let result = attempt_rotation(ars, &t, &field, RotationDirection::Clockwise);
+        assert_eq!(result.kick_index, 1);
+        assert_eq!(result.tetromino.x, 0);
+
+        // 中央列ルール: 中央列が先にぶつかる場合は壁蹴りしない
+        // （ルールがなければ右へ1マスずれて回転できる配置）
+        field.cells[3 * 10 + 5] = 1;
+        let t = Tetromino { kind: TetrominoKind::T, x: 4, y: 3, rotation: 0 };
+        assert!(!check_collision(ars, &field, &t));
+        let result = attempt_rotation(ars, &t, &field, RotationDirection::Clockwise);
+        assert_eq!(result.kick_index, -1);
+    }
+
+    #[test]
+    fn test_buffer_rows_are_open_space() {
+        // バッファ内は空きマスとして扱い、天井扱いでT-Spinにならない
+        let field = Field { width: 10, height: 40, cells: vec![0; 400] };
+        let t = Tetromino { kind: TetrominoKind::T, x: 4, y: 19, rotation: 2 };
+        assert!(!check_collision(srs(), &field, &t));
+        assert!(!field.is_filled(3, 19));
+        assert_eq!(detect_t_spin(srs(), &t, &field, false, 0, CW), TSpinType::None);
+
+        // 可視部分だけを切り出す
+        let mut field = field;
+        field.cells[19 * 10] = 1;
+        field.cells[20 * 10] = 2;
+        let visible = BitBoard::from_field(&field).visible_part(20);
+        assert_eq!(visible.height, 20);
+        assert_eq!(visible.cells[0], 2);
+        assert!(visible.cells.iter().filter(|&&c| c != 0).count() == 1);
+    }
+}
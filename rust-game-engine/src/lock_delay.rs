@@ -0,0 +1,142 @@
+pub const DEFAULT_LOCK_DELAY_MS: u32 = 500;
+pub const DEFAULT_MAX_LOCK_RESETS: u32 = 15;
+
+// 接地後の固定猶予（Extended Placement）
+// - 接地中は時間経過で猶予を消費し、delay_msに達したら固定
+// - 移動・回転に成功すると猶予がリセットされる（最大max_resets回まで）
+// - それまでより低い行に到達すると、リセット回数も含めて全てリセット
+pub struct LockDelay {
+    delay_ms: u32,
+    max_resets: u32,
+    elapsed_ms: u32,
+    resets: u32,
+    lowest_y: i32,
+    touched: bool,
+}
+
+impl Default for LockDelay {
+    fn default() -> Self {
+        Self::new(DEFAULT_LOCK_DELAY_MS, DEFAULT_MAX_LOCK_RESETS)
+    }
+}
+
+impl LockDelay {
+    pub fn new(delay_ms: u32, max_resets: u32) -> Self {
+        Self {
+            delay_ms,
+            max_resets,
+            elapsed_ms: 0,
+            resets: 0,
+            lowest_y: i32::MIN,
+            touched: false,
+        }
+    }
+
+    pub fn configure(&mut self, delay_ms: u32, max_resets: u32) {
+        self.delay_ms = delay_ms;
+        self.max_resets = max_resets;
+    }
+
+    // 新しいミノの出現時に呼ぶ
+    pub fn start(&mut self, spawn_y: i32) {
+        self.elapsed_ms = 0;
+        self.resets = 0;
+        self.lowest_y = spawn_y;
+        self.touched = false;
+    }
+
+    // 移動・回転に成功した後に呼ぶ
+    pub fn on_move(&mut self, y: i32) {
+        if y > self.lowest_y {
+            self.lowest_y = y;
+            self.elapsed_ms = 0;
+            self.resets = 0;
+            self.touched = false;
+        } else if self.touched {
+            self.elapsed_ms = 0;
+            self.resets += 1;
+        }
+    }
+
+    // 自然落下後に呼ぶ（落下はリセット回数に数えない）
+    pub fn on_fall(&mut self, y: i32) {
+        if y > self.lowest_y {
+            self.on_move(y);
+        }
+    }
+
+    // 時間を進める。固定すべきならtrue
+    pub fn advance(&mut self, dt_ms: u32, grounded: bool) -> bool {
+        if !grounded {
+            return false;
+        }
+        self.touched = true;
+        if self.resets_exhausted() {
+            return true;
+        }
+        self.elapsed_ms = self.elapsed_ms.saturating_add(dt_ms);
+        self.elapsed_ms >= self.delay_ms
+    }
+
+    // リセット上限に達していれば、接地した時点で即固定する
+    pub fn resets_exhausted(&self) -> bool {
+        self.resets >= self.max_resets
+    }
+
+    pub fn remaining_ms(&self) -> u32 {
+        self.delay_ms.saturating_sub(self.elapsed_ms)
+    }
+
+    pub fn resets(&self) -> u32 {
+        self.resets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locks_after_delay_while_grounded() {
+        let mut lock = LockDelay::new(500, 15);
+        lock.start(0);
+        assert!(!lock.advance(1000, false));
+        assert!(!lock.advance(300, true));
+        assert!(lock.advance(200, true));
+    }
+
+    #[test]
+    fn test_move_resets_timer_up_to_limit() {
+        let mut lock = LockDelay::new(500, 3);
+        lock.start(18);
+        for _ in 0..3 {
+            assert!(!lock.advance(400, true));
+            lock.on_move(18);
+        }
+        assert_eq!(lock.resets(), 3);
+        assert!(lock.advance(0, true));
+    }
+
+    #[test]
+    fn test_new_lowest_row_resets_counter() {
+        let mut lock = LockDelay::new(500, 2);
+        lock.start(10);
+        lock.advance(100, true);
+        lock.on_move(10);
+        lock.on_move(10);
+        assert!(lock.resets_exhausted());
+        lock.on_move(11);
+        assert_eq!(lock.resets(), 0);
+        assert_eq!(lock.remaining_ms(), 500);
+        assert!(!lock.advance(100, true));
+    }
+
+    #[test]
+    fn test_moves_before_touching_ground_do_not_count() {
+        let mut lock = LockDelay::new(500, 15);
+        lock.start(0);
+        lock.on_move(0);
+        lock.on_move(0);
+        assert_eq!(lock.resets(), 0);
+    }
+}
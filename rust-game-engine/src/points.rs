@@ -0,0 +1,141 @@
+use serde::{Deserialize, Serialize};
+
+pub const PLACEMENT_POINTS: u32 = 10;
+pub const HOLD_COST: u32 = 15;
+pub const LINE_DELETE_COST: u32 = 200;
+// エクスチェンジの累積コスト（5回目以降は最大値のまま）
+pub const EXCHANGE_COSTS: [u32; 5] = [45, 65, 90, 120, 160];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PointsSource {
+    Placement,
+    Achievement,
+    RankBonus,
+    Exchange,
+    Hold,
+    LineDelete,
+}
+
+// ポイントの出入り1件分（UI表示・バックエンドでの監査用）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PointsTransaction {
+    pub source: PointsSource,
+    // 獲得は正、消費は負
+    pub amount: i64,
+    // 取引後の残高
+    pub balance: u32,
+    // フィーバー中で無料になった消費
+    pub waived: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InsufficientPoints {
+    pub cost: u32,
+    pub balance: u32,
+}
+
+// スコアとは別のポイント通貨の台帳
+#[derive(Default)]
+pub struct PointsLedger {
+    balance: u32,
+    exchange_count: u32,
+    total_exchanges: u32,
+    history: Vec<PointsTransaction>,
+}
+
+impl PointsLedger {
+    pub fn balance(&self) -> u32 {
+        self.balance
+    }
+
+    pub fn history(&self) -> &[PointsTransaction] {
+        &self.history
+    }
+
+    // このゲームで行ったエクスチェンジの回数
+    pub fn total_exchanges(&self) -> u32 {
+        self.total_exchanges
+    }
+
+    // 次のエクスチェンジのコスト（ミノを固定するとリセット）
+    pub fn exchange_cost(&self) -> u32 {
+        let index = (self.exchange_count as usize).min(EXCHANGE_COSTS.len() - 1);
+        EXCHANGE_COSTS[index]
+    }
+
+    pub fn earn(&mut self, source: PointsSource, amount: u32) -> PointsTransaction {
+        self.balance = self.balance.saturating_add(amount);
+        self.record(source, amount as i64, false)
+    }
+
+    // コストを支払う。waivedならコスト0で記録だけ残す
+    pub fn spend(&mut self, source: PointsSource, cost: u32, waived: bool) -> Result<PointsTransaction, InsufficientPoints> {
+        if waived {
+            return Ok(self.record(source, 0, true));
+        }
+        if self.balance < cost {
+            return Err(InsufficientPoints { cost, balance: self.balance });
+        }
+        self.balance -= cost;
+        Ok(self.record(source, -(cost as i64), false))
+    }
+
+    // エクスチェンジ。フィーバー中は無料で、累積コストも上がらない
+    pub fn spend_exchange(&mut self, fever: bool) -> Result<PointsTransaction, InsufficientPoints> {
+        let transaction = self.spend(PointsSource::Exchange, self.exchange_cost(), fever)?;
+        if !fever {
+            self.exchange_count += 1;
+        }
+        self.total_exchanges += 1;
+        Ok(transaction)
+    }
+
+    // ミノ固定時: 配置ポイントを加算し、エクスチェンジの累積コストをリセット
+    pub fn on_placement(&mut self) -> PointsTransaction {
+        self.exchange_count = 0;
+        self.earn(PointsSource::Placement, PLACEMENT_POINTS)
+    }
+
+    fn record(&mut self, source: PointsSource, amount: i64, waived: bool) -> PointsTransaction {
+        let transaction = PointsTransaction { source, amount, balance: self.balance, waived };
+        self.history.push(transaction.clone());
+        transaction
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exchange_cost_escalates_and_resets_on_placement() {
+        let mut ledger = PointsLedger::default();
+        ledger.earn(PointsSource::Achievement, 1000);
+        let costs: Vec<i64> = (0..6).map(|_| ledger.spend_exchange(false).unwrap().amount).collect();
+        assert_eq!(costs, vec![-45, -65, -90, -120, -160, -160]);
+        ledger.on_placement();
+        assert_eq!(ledger.exchange_cost(), 45);
+        assert_eq!(ledger.total_exchanges(), 6);
+    }
+
+    #[test]
+    fn test_insufficient_points() {
+        let mut ledger = PointsLedger::default();
+        ledger.on_placement();
+        let err = ledger.spend(PointsSource::Hold, HOLD_COST, false).unwrap_err();
+        assert_eq!(err, InsufficientPoints { cost: HOLD_COST, balance: PLACEMENT_POINTS });
+        assert_eq!(ledger.balance(), PLACEMENT_POINTS);
+        assert_eq!(ledger.history().len(), 1);
+    }
+
+    #[test]
+    fn test_fever_waives_cost() {
+        let mut ledger = PointsLedger::default();
+        let transaction = ledger.spend_exchange(true).unwrap();
+        assert!(transaction.waived);
+        assert_eq!(transaction.amount, 0);
+        assert_eq!(ledger.exchange_cost(), EXCHANGE_COSTS[0]);
+        let transaction = ledger.spend(PointsSource::LineDelete, LINE_DELETE_COST, true).unwrap();
+        assert_eq!(transaction.balance, 0);
+    }
+}
@@ -0,0 +1,89 @@
+use crate::randomizer::{create_randomizer, Randomizer, RandomizerKind};
+use crate::TetrominoKind;
+use std::collections::VecDeque;
+
+pub const MIN_PREVIEW: usize = 1;
+pub const MAX_PREVIEW: usize = 7;
+pub const DEFAULT_PREVIEW: usize = 5;
+
+// NEXT表示用の先読みキュー
+// 表示数を減らしても先読み済みのミノは捨てないので、表示数に関係なくミノ順は同じ
+pub struct NextQueue {
+    randomizer: Box<dyn Randomizer>,
+    queue: VecDeque<TetrominoKind>,
+    preview: usize,
+}
+
+impl NextQueue {
+    pub fn new(kind: RandomizerKind, seed: u32, preview: usize) -> Self {
+        let mut next = Self {
+            randomizer: create_randomizer(kind, seed),
+            queue: VecDeque::with_capacity(MAX_PREVIEW + 1),
+            preview: preview.clamp(MIN_PREVIEW, MAX_PREVIEW),
+        };
+        next.fill();
+        next
+    }
+
+    fn fill(&mut self) {
+        while self.queue.len() < self.preview {
+            self.queue.push_back(self.randomizer.next_kind());
+        }
+    }
+
+    // 先頭のミノを取り出し、表示数分を補充する
+    pub fn pop(&mut self) -> TetrominoKind {
+        let kind = self
+            .queue
+            .pop_front()
+            .unwrap_or_else(|| self.randomizer.next_kind());
+        self.fill();
+        kind
+    }
+
+    pub fn preview(&self) -> Vec<TetrominoKind> {
+        self.queue.iter().take(self.preview).copied().collect()
+    }
+
+    pub fn preview_len(&self) -> usize {
+        self.preview
+    }
+
+    pub fn set_preview_len(&mut self, preview: usize) {
+        self.preview = preview.clamp(MIN_PREVIEW, MAX_PREVIEW);
+        self.fill();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preview_matches_popped_order() {
+        let mut next = NextQueue::new(RandomizerKind::SevenBag, 99, 5);
+        let preview = next.preview();
+        assert_eq!(preview.len(), 5);
+        let popped: Vec<TetrominoKind> = (0..5).map(|_| next.pop()).collect();
+        assert_eq!(preview, popped);
+        assert_eq!(next.preview().len(), 5);
+    }
+
+    #[test]
+    fn test_preview_length_is_clamped() {
+        let mut next = NextQueue::new(RandomizerKind::SevenBag, 1, 0);
+        assert_eq!(next.preview_len(), MIN_PREVIEW);
+        next.set_preview_len(20);
+        assert_eq!(next.preview().len(), MAX_PREVIEW);
+    }
+
+    #[test]
+    fn test_preview_length_does_not_change_order() {
+        let mut short = NextQueue::new(RandomizerKind::SevenBag, 5, 1);
+        let mut long = NextQueue::new(RandomizerKind::SevenBag, 5, 7);
+        long.set_preview_len(2);
+        let a: Vec<TetrominoKind> = (0..14).map(|_| short.pop()).collect();
+        let b: Vec<TetrominoKind> = (0..14).map(|_| long.pop()).collect();
+        assert_eq!(a, b);
+    }
+}
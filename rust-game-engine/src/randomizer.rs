@@ -0,0 +1,209 @@
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+use crate::TetrominoKind;
+
+// ミノ順を決める乱数器の共通インターフェース
+// 同じシードからは常に同じミノ順が得られること
+pub trait Randomizer {
+    fn next_kind(&mut self) -> TetrominoKind;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RandomizerKind {
+    SevenBag,
+    FourteenBag,
+    History,
+    Pure,
+}
+
+impl RandomizerKind {
+    // JS側から渡される名前を解釈（未知の名前はNone）
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "7bag" | "bag" => Some(RandomizerKind::SevenBag),
+            "14bag" => Some(RandomizerKind::FourteenBag),
+            "history" | "tgm" => Some(RandomizerKind::History),
+            "random" | "pure" => Some(RandomizerKind::Pure),
+            _ => None,
+        }
+    }
+}
+
+pub fn create_randomizer(kind: RandomizerKind, seed: u32) -> Box<dyn Randomizer> {
+    match kind {
+        RandomizerKind::SevenBag => Box::new(BagRandomizer::new(seed, 1)),
+        RandomizerKind::FourteenBag => Box::new(BagRandomizer::new(seed, 2)),
+        RandomizerKind::History => Box::new(HistoryRandomizer::new(seed)),
+        RandomizerKind::Pure => Box::new(PureRandomizer::new(seed)),
+    }
+}
+
+fn seeded_rng(seed: u32) -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(seed as u64)
+}
+
+// ガイドライン準拠のバッグ方式（copies=1で7バッグ, copies=2で14バッグ）
+pub struct BagRandomizer {
+    rng: ChaCha8Rng,
+    bag: Vec<TetrominoKind>,
+    copies: usize,
+}
+
+impl BagRandomizer {
+    pub fn new(seed: u32, copies: usize) -> Self {
+        Self {
+            rng: seeded_rng(seed),
+            bag: Vec::with_capacity(TetrominoKind::ALL.len() * copies),
+            copies,
+        }
+    }
+
+    fn refill(&mut self) {
+        for _ in 0..self.copies {
+            self.bag.extend_from_slice(&TetrominoKind::ALL);
+        }
+        self.bag.shuffle(&mut self.rng);
+    }
+}
+
+impl Randomizer for BagRandomizer {
+    fn next_kind(&mut self) -> TetrominoKind {
+        if self.bag.is_empty() {
+            self.refill();
+        }
+        self.bag.pop().unwrap()
+    }
+}
+
+// TGM方式の履歴乱数器
+// 直近4ミノの履歴と被った場合は最大4回まで引き直す。初手はS/Z/Oを出さない
+pub struct HistoryRandomizer {
+    rng: ChaCha8Rng,
+    history: VecDeque<TetrominoKind>,
+    first: bool,
+}
+
+const HISTORY_SIZE: usize = 4;
+const HISTORY_ROLLS: usize = 4;
+
+impl HistoryRandomizer {
+    pub fn new(seed: u32) -> Self {
+        Self {
+            rng: seeded_rng(seed),
+            history: VecDeque::from(vec![TetrominoKind::Z; HISTORY_SIZE]),
+            first: true,
+        }
+    }
+
+    fn roll(&mut self) -> TetrominoKind {
+        TetrominoKind::ALL[self.rng.gen_range(0..TetrominoKind::ALL.len())]
+    }
+}
+
+impl Randomizer for HistoryRandomizer {
+    fn next_kind(&mut self) -> TetrominoKind {
+        let kind = if self.first {
+            self.first = false;
+            loop {
+                let kind = self.roll();
+                if !matches!(kind, TetrominoKind::S | TetrominoKind::Z | TetrominoKind::O) {
+                    break kind;
+                }
+            }
+        } else {
+            let mut kind = self.roll();
+            for _ in 1..HISTORY_ROLLS {
+                if !self.history.contains(&kind) {
+                    break;
+                }
+                kind = self.roll();
+            }
+            kind
+        };
+        self.history.pop_front();
+        self.history.push_back(kind);
+        kind
+    }
+}
+
+// 完全ランダム（従来の挙動をシード付きで再現）
+pub struct PureRandomizer {
+    rng: ChaCha8Rng,
+}
+
+impl PureRandomizer {
+    pub fn new(seed: u32) -> Self {
+        Self { rng: seeded_rng(seed) }
+    }
+}
+
+impl Randomizer for PureRandomizer {
+    fn next_kind(&mut self) -> TetrominoKind {
+        TetrominoKind::ALL[self.rng.gen_range(0..TetrominoKind::ALL.len())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draw(randomizer: &mut dyn Randomizer, n: usize) -> Vec<TetrominoKind> {
+        (0..n).map(|_| randomizer.next_kind()).collect()
+    }
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        for kind in [
+            RandomizerKind::SevenBag,
+            RandomizerKind::FourteenBag,
+            RandomizerKind::History,
+            RandomizerKind::Pure,
+        ] {
+            let a = draw(create_randomizer(kind, 12345).as_mut(), 100);
+            let b = draw(create_randomizer(kind, 12345).as_mut(), 100);
+            assert_eq!(a, b, "{:?}", kind);
+        }
+    }
+
+    #[test]
+    fn test_seven_bag_contains_each_piece_once() {
+        let mut randomizer = BagRandomizer::new(42, 1);
+        for _ in 0..10 {
+            let mut bag = draw(&mut randomizer, 7);
+            bag.sort();
+            let mut expected = TetrominoKind::ALL.to_vec();
+            expected.sort();
+            assert_eq!(bag, expected);
+        }
+    }
+
+    #[test]
+    fn test_fourteen_bag_contains_each_piece_twice() {
+        let mut randomizer = BagRandomizer::new(7, 2);
+        let bag = draw(&mut randomizer, 14);
+        for kind in TetrominoKind::ALL {
+            assert_eq!(bag.iter().filter(|&&k| k == kind).count(), 2);
+        }
+    }
+
+    #[test]
+    fn test_history_first_piece_is_not_s_z_o() {
+        for seed in 0..200 {
+            let first = HistoryRandomizer::new(seed).next_kind();
+            assert!(!matches!(first, TetrominoKind::S | TetrominoKind::Z | TetrominoKind::O));
+        }
+    }
+
+    #[test]
+    fn test_parse_randomizer_kind() {
+        assert_eq!(RandomizerKind::parse("7bag"), Some(RandomizerKind::SevenBag));
+        assert_eq!(RandomizerKind::parse("14bag"), Some(RandomizerKind::FourteenBag));
+        assert_eq!(RandomizerKind::parse("tgm"), Some(RandomizerKind::History));
+        assert_eq!(RandomizerKind::parse("random"), Some(RandomizerKind::Pure));
+        assert_eq!(RandomizerKind::parse("unknown"), None);
+    }
+}
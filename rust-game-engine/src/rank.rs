@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+
+// 段位昇格時のポイントボーナス（1段位につき）
+pub const PROMOTION_BONUS_POINTS: u32 = 50;
+
+pub struct DanRankDef {
+    pub name: &'static str,
+    pub threshold: u32,
+    pub color: &'static str,
+    pub description: &'static str,
+}
+
+// 段位テーブル（14段階）
+pub const DAN_RANKS: [DanRankDef; 14] = [
+    DanRankDef { name: "無段", threshold: 0, color: "#666666", description: "テトリスの基礎を学ぶ段階" },
+    DanRankDef { name: "初段", threshold: 200, color: "#8B4513", description: "基本操作をマスターした初心者" },
+    DanRankDef { name: "二段", threshold: 800, color: "#A0522D", description: "ライン消去の技術を習得" },
+    DanRankDef { name: "三段", threshold: 2_000, color: "#CD853F", description: "T-Spinの基礎を理解" },
+    DanRankDef { name: "四段", threshold: 4_000, color: "#DEB887", description: "多様なスピン技を習得" },
+    DanRankDef { name: "五段", threshold: 8_000, color: "#F5DEB3", description: "コンボシステムをマスター" },
+    DanRankDef { name: "六段", threshold: 15_000, color: "#C0C0C0", description: "高速プレイが可能" },
+    DanRankDef { name: "七段", threshold: 25_000, color: "#D3D3D3", description: "上級テクニックを習得" },
+    DanRankDef { name: "八段", threshold: 40_000, color: "#E6E6FA", description: "極めて高い技術力" },
+    DanRankDef { name: "九段", threshold: 60_000, color: "#FFD700", description: "達人レベルの実力" },
+    DanRankDef { name: "十段", threshold: 90_000, color: "#FFA500", description: "最高峰の技術を持つ" },
+    DanRankDef { name: "名人", threshold: 130_000, color: "#FF4500", description: "伝説的なプレイヤー" },
+    DanRankDef { name: "竜王", threshold: 200_000, color: "#DC143C", description: "圧倒的な実力の持ち主" },
+    DanRankDef { name: "永世名人", threshold: 300_000, color: "#8A2BE2", description: "ClaudeTetrisの頂点に立つ者" },
+];
+
+// スコアに対応する段位のインデックス
+pub fn rank_for_score(score: u32) -> usize {
+    DAN_RANKS.iter().rposition(|r| score >= r.threshold).unwrap_or(0)
+}
+
+// UI表示用の段位情報
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RankInfo {
+    pub rank: u8,
+    pub name: String,
+    pub color: String,
+    pub description: String,
+    pub threshold: u32,
+    // 最高段位ならNone
+    pub next_threshold: Option<u32>,
+    // 次の段位までの進捗（0.0〜1.0、最高段位は1.0）
+    pub progress: f64,
+}
+
+impl RankInfo {
+    pub fn for_score(score: u32) -> Self {
+        let rank = rank_for_score(score);
+        let def = &DAN_RANKS[rank];
+        let next_threshold = DAN_RANKS.get(rank + 1).map(|r| r.threshold);
+        let progress = match next_threshold {
+            Some(next) => (score - def.threshold) as f64 / (next - def.threshold) as f64,
+            None => 1.0,
+        };
+        Self {
+            rank: rank as u8,
+            name: def.name.to_string(),
+            color: def.color.to_string(),
+            description: def.description.to_string(),
+            threshold: def.threshold,
+            next_threshold,
+            progress,
+        }
+    }
+}
+
+// 段位昇格イベント
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RankPromotion {
+    pub from: u8,
+    pub to: u8,
+    pub name: String,
+    pub bonus_points: u32,
+}
+
+// 現在の段位を保持し、スコア更新時に昇格を検出する
+#[derive(Default)]
+pub struct DanRank {
+    current: usize,
+}
+
+impl DanRank {
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    // 段位の閾値を超えたら昇格イベントを返す（一度に複数段上がればボーナスも段数分）
+    pub fn update(&mut self, score: u32) -> Option<RankPromotion> {
+        let rank = rank_for_score(score);
+        if rank <= self.current {
+            return None;
+        }
+        let promotion = RankPromotion {
+            from: self.current as u8,
+            to: rank as u8,
+            name: DAN_RANKS[rank].name.to_string(),
+            bonus_points: PROMOTION_BONUS_POINTS * (rank - self.current) as u32,
+        };
+        self.current = rank;
+        Some(promotion)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rank_for_score() {
+        assert_eq!(rank_for_score(0), 0);
+        assert_eq!(rank_for_score(199), 0);
+        assert_eq!(rank_for_score(200), 1);
+        assert_eq!(rank_for_score(129_999), 10);
+        assert_eq!(rank_for_score(300_000), 13);
+        assert_eq!(rank_for_score(u32::MAX), 13);
+    }
+
+    #[test]
+    fn test_rank_info_progress() {
+        let info = RankInfo::for_score(500);
+        assert_eq!(info.name, "初段");
+        assert_eq!(info.next_threshold, Some(800));
+        assert!((info.progress - 0.5).abs() < 1e-9);
+        let top = RankInfo::for_score(400_000);
+        assert_eq!(top.next_threshold, None);
+        assert_eq!(top.progress, 1.0);
+    }
+
+    #[test]
+    fn test_promotion_event() {
+        let mut rank = DanRank::default();
+        assert!(rank.update(150).is_none());
+        let promotion = rank.update(250).unwrap();
+        assert_eq!((promotion.from, promotion.to), (0, 1));
+        assert_eq!(promotion.bonus_points, PROMOTION_BONUS_POINTS);
+        assert!(rank.update(300).is_none());
+        // 2段飛ばし
+        let promotion = rank.update(2_500).unwrap();
+        assert_eq!(promotion.to, 3);
+        assert_eq!(promotion.bonus_points, PROMOTION_BONUS_POINTS * 2);
+    }
+}
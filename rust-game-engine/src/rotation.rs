@@ -0,0 +1,466 @@
+use crate::{Occupancy, Tetromino, TetrominoKind};
+use serde::{Deserialize, Serialize};
+
+// 回転ごとの4マス相対座標
+pub type ShapeRotations = [[(i32, i32); 4]; 4];
+
+// 壁蹴りのオフセット（キックテーブルの値をそのままy座標に加算する）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KickOffset {
+    pub x: i32,
+    pub y: i32,
+}
+
+// 回転法則の共通インターフェース
+// 形状（回転状態ごとの4マス）・出現時の向き・壁蹴りを提供する
+pub trait RotationSystem {
+    fn shape(&self, kind: TetrominoKind, rotation: u8) -> &'static [(i32, i32); 4];
+
+    // 出現時の回転状態
+    fn spawn_rotation(&self, _kind: TetrominoKind) -> u8 {
+        0
+    }
+
+    // from→toの回転で試す位置（先頭は壁蹴りなし）
+    fn kicks(&self, kind: TetrominoKind, from: u8, to: u8) -> &'static [KickOffset];
+
+    // 出現位置（形状の左上基準の座標）
+    // 出現時の向きで中央（奇数余りは左寄り）に置き、最下段を visible_top-1（21段目）に合わせる
+    // バッファがなく上にはみ出す場合は最上段を y=0 に合わせる
+    fn spawn_position(&self, kind: TetrominoKind, width: u32, visible_top: i32) -> (i32, i32) {
+        let shape = self.shape(kind, self.spawn_rotation(kind));
+        let min_x = shape.iter().map(|&(x, _)| x).min().unwrap_or(0);
+        let max_x = shape.iter().map(|&(x, _)| x).max().unwrap_or(0);
+        let min_y = shape.iter().map(|&(_, y)| y).min().unwrap_or(0);
+        let max_y = shape.iter().map(|&(_, y)| y).max().unwrap_or(0);
+        let piece_width = max_x - min_x + 1;
+        let x = (width as i32 - piece_width).div_euclid(2) - min_x;
+        let y = (visible_top - 1 - max_y).max(-min_y);
+        (x, y)
+    }
+
+    // スピンの角判定の基準にする中心（形状の左上基準、Iミノ以外）
+    // 既定では形状から求める（回転の中心を持たない回転法則向け）
+    fn corner_center(&self, kind: TetrominoKind, rotation: u8) -> (i32, i32) {
+        shape_center(self.shape(kind, rotation))
+    }
+
+    // その場で回転できなかったとき壁蹴りを試してよいか
+    // rotated: 壁蹴りなしで回転させた状態
+    fn allows_kick(&self, _rotated: &Tetromino, _field: &dyn Occupancy) -> bool {
+        true
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RotationSystemKind {
+    Srs,
+    Ars,
+    Nrs,
+}
+
+impl RotationSystemKind {
+    // JS側から渡される名前を解釈（未知の名前はNone）
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "srs" | "guideline" => Some(RotationSystemKind::Srs),
+            "ars" | "tgm" => Some(RotationSystemKind::Ars),
+            "nrs" | "classic" => Some(RotationSystemKind::Nrs),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RotationSystemKind::Srs => "srs",
+            RotationSystemKind::Ars => "ars",
+            RotationSystemKind::Nrs => "nrs",
+        }
+    }
+
+    // 回転法則は状態を持たないので静的なインスタンスを返す
+    pub fn system(self) -> &'static dyn RotationSystem {
+        match self {
+            RotationSystemKind::Srs => &SuperRotationSystem,
+            RotationSystemKind::Ars => &ArikaRotationSystem,
+            RotationSystemKind::Nrs => &NintendoRotationSystem,
+        }
+    }
+}
+
+// 形状テーブルはTetrominoKind::ALLの順に並べる
+fn shape_of(table: &'static [ShapeRotations; 7], kind: TetrominoKind, rotation: u8) -> &'static [(i32, i32); 4] {
+    &table[kind.index()][(rotation % 4) as usize]
+}
+
+const NO_KICK: &[KickOffset] = &[KickOffset { x: 0, y: 0 }];
+
+// 3x2の形状の中心: 長辺の中央で、ブロックが3つ並んだ行・列
+// S/Zは横向きなら下の行、縦向きなら左の列（SRSの出現時・右向きと同じ位置）
+fn shape_center(shape: &[(i32, i32); 4]) -> (i32, i32) {
+    let min_x = shape.iter().map(|&(x, _)| x).min().unwrap_or(0);
+    let max_x = shape.iter().map(|&(x, _)| x).max().unwrap_or(0);
+    let min_y = shape.iter().map(|&(_, y)| y).min().unwrap_or(0);
+    let max_y = shape.iter().map(|&(_, y)| y).max().unwrap_or(0);
+    let in_row = |y: i32| shape.iter().filter(|&&(_, by)| by == y).count();
+    let in_column = |x: i32| shape.iter().filter(|&&(bx, _)| bx == x).count();
+    if max_x - min_x >= max_y - min_y {
+        let y = if in_row(min_y) > in_row(max_y) { min_y } else { max_y };
+        (min_x + 1, y)
+    } else {
+        let x = if in_column(max_x) > in_column(min_x) { max_x } else { min_x };
+        (x, min_y + 1)
+    }
+}
+
+// テトリミノ形状・回転ごとの4マス相対座標テーブル（SRS）
+static SRS_SHAPES: [ShapeRotations; 7] = [
+    // Iミノ
+    [
+        [(0, 1), (1, 1), (2, 1), (3, 1)], // 0度
+        [(2, 0), (2, 1), (2, 2), (2, 3)], // 90度
+        [(0, 2), (1, 2), (2, 2), (3, 2)], // 180度
+        [(1, 0), (1, 1), (1, 2), (1, 3)], // 270度
+    ],
+    // Oミノ
+    [
+        [(1, 0), (2, 0), (1, 1), (2, 1)], // 0度
+        [(1, 0), (2, 0), (1, 1), (2, 1)], // 90度
+        [(1, 0), (2, 0), (1, 1), (2, 1)], // 180度
+        [(1, 0), (2, 0), (1, 1), (2, 1)], // 270度
+    ],
+    // Tミノ
+    [
+        [(1, 0), (0, 1), (1, 1), (2, 1)], // 0度
+        [(1, 0), (1, 1), (2, 1), (1, 2)], // 90度
+        [(0, 1), (1, 1), (2, 1), (1, 2)], // 180度
+        [(1, 0), (0, 1), (1, 1), (1, 2)], // 270度
+    ],
+    // Sミノ
+    [
+        [(1, 0), (2, 0), (0, 1), (1, 1)], // 0度
+        [(1, 0), (1, 1), (2, 1), (2, 2)], // 90度
+        [(1, 1), (2, 1), (0, 2), (1, 2)], // 180度
+        [(0, 0), (0, 1), (1, 1), (1, 2)], // 270度
+    ],
+    // Zミノ
+    [
+        [(0, 0), (1, 0), (1, 1), (2, 1)], // 0度
+        [(2, 0), (1, 1), (2, 1), (1, 2)], // 90度
+        [(0, 1), (1, 1), (1, 2), (2, 2)], // 180度
+        [(1, 0), (0, 1), (1, 1), (0, 2)], // 270度
+    ],
+    // Jミノ
+    [
+        [(0, 0), (0, 1), (1, 1), (2, 1)], // 0度
+        [(1, 0), (2, 0), (1, 1), (1, 2)], // 90度
+        [(0, 1), (1, 1), (2, 1), (2, 2)], // 180度
+        [(1, 0), (1, 1), (0, 2), (1, 2)], // 270度
+    ],
+    // Lミノ
+    [
+        [(2, 0), (0, 1), (1, 1), (2, 1)], // 0度
+        [(1, 0), (1, 1), (1, 2), (2, 2)], // 90度
+        [(0, 1), (1, 1), (2, 1), (0, 2)], // 180度
+        [(0, 0), (1, 0), (1, 1), (1, 2)], // 270度
+    ],
+];
+
+// ガイドライン準拠のSRS（180度回転はSRS+ / TETR.IO準拠）
+pub struct SuperRotationSystem;
+
+impl SuperRotationSystem {
+    // 通常ミノ用SRSキックテーブル
+    fn jlstz_kicks(from: u8, to: u8) -> &'static [KickOffset] {
+        match (from, to) {
+            (0, 1) | (2, 1) => &[KickOffset {x:0, y:0}, KickOffset {x:-1, y:0}, KickOffset {x:-1, y:1}, KickOffset {x:0, y:-2}, KickOffset {x:-1, y:-2}],
+            (1, 0) | (1, 2) => &[KickOffset {x:0, y:0}, KickOffset {x:1, y:0}, KickOffset {x:1, y:-1}, KickOffset {x:0, y:2}, KickOffset {x:1, y:2}],
+            (2, 3) | (0, 3) => &[KickOffset {x:0, y:0}, KickOffset {x:1, y:0}, KickOffset {x:1, y:1}, KickOffset {x:0, y:-2}, KickOffset {x:1, y:-2}],
+            (3, 2) | (3, 0) => &[KickOffset {x:0, y:0}, KickOffset {x:-1, y:0}, KickOffset {x:-1, y:-1}, KickOffset {x:0, y:2}, KickOffset {x:-1, y:2}],
+            (0, 2) => &[KickOffset {x:0, y:0}, KickOffset {x:0, y:1}, KickOffset {x:1, y:1}, KickOffset {x:-1, y:1}, KickOffset {x:1, y:0}, KickOffset {x:-1, y:0}],
+            (2, 0) => &[KickOffset {x:0, y:0}, KickOffset {x:0, y:-1}, KickOffset {x:-1, y:-1}, KickOffset {x:1, y:-1}, KickOffset {x:-1, y:0}, KickOffset {x:1, y:0}],
+            (1, 3) => &[KickOffset {x:0, y:0}, KickOffset {x:1, y:0}, KickOffset {x:1, y:2}, KickOffset {x:1, y:1}, KickOffset {x:0, y:2}, KickOffset {x:0, y:1}],
+            (3, 1) => &[KickOffset {x:0, y:0}, KickOffset {x:-1, y:0}, KickOffset {x:-1, y:2}, KickOffset {x:-1, y:1}, KickOffset {x:0, y:2}, KickOffset {x:0, y:1}],
+            _ => NO_KICK,
+        }
+    }
+
+    // Iミノ専用SRSキックテーブル
+    fn i_kicks(from: u8, to: u8) -> &'static [KickOffset] {
+        match (from, to) {
+            (0, 1) | (3, 2) => &[KickOffset {x:0, y:0}, KickOffset {x:-2, y:0}, KickOffset {x:1, y:0}, KickOffset {x:-2, y:-1}, KickOffset {x:1, y:2}],
+            (1, 0) | (2, 3) => &[KickOffset {x:0, y:0}, KickOffset {x:2, y:0}, KickOffset {x:-1, y:0}, KickOffset {x:2, y:1}, KickOffset {x:-1, y:-2}],
+            (1, 2) | (0, 3) => &[KickOffset {x:0, y:0}, KickOffset {x:-1, y:0}, KickOffset {x:2, y:0}, KickOffset {x:-1, y:2}, KickOffset {x:2, y:-1}],
+            (2, 1) | (3, 0) => &[KickOffset {x:0, y:0}, KickOffset {x:1, y:0}, KickOffset {x:-2, y:0}, KickOffset {x:1, y:-2}, KickOffset {x:-2, y:1}],
+            (0, 2) => &[KickOffset {x:0, y:0}, KickOffset {x:0, y:1}],
+            (2, 0) => &[KickOffset {x:0, y:0}, KickOffset {x:0, y:-1}],
+            (1, 3) => &[KickOffset {x:0, y:0}, KickOffset {x:1, y:0}],
+            (3, 1) => &[KickOffset {x:0, y:0}, KickOffset {x:-1, y:0}],
+            _ => NO_KICK,
+        }
+    }
+}
+
+impl RotationSystem for SuperRotationSystem {
+    fn shape(&self, kind: TetrominoKind, rotation: u8) -> &'static [(i32, i32); 4] {
+        shape_of(&SRS_SHAPES, kind, rotation)
+    }
+
+    // SRSは3x3の枠の中央を中心に回転する
+    fn corner_center(&self, _kind: TetrominoKind, _rotation: u8) -> (i32, i32) {
+        (1, 1)
+    }
+
+    fn kicks(&self, kind: TetrominoKind, from: u8, to: u8) -> &'static [KickOffset] {
+        match kind {
+            TetrominoKind::I => Self::i_kicks(from, to),
+            TetrominoKind::O => NO_KICK,
+            _ => Self::jlstz_kicks(from, to),
+        }
+    }
+}
+
+// ARS（TGM）の形状テーブル
+// 3x3の枠で下詰め、T/J/Lは平らな面を上にして出現する
+static ARS_SHAPES: [ShapeRotations; 7] = [
+    // Iミノ（横・縦の2状態）
+    [
+        [(0, 1), (1, 1), (2, 1), (3, 1)],
+        [(2, 0), (2, 1), (2, 2), (2, 3)],
+        [(0, 1), (1, 1), (2, 1), (3, 1)],
+        [(2, 0), (2, 1), (2, 2), (2, 3)],
+    ],
+    // Oミノ
+    [
+        [(1, 1), (2, 1), (1, 2), (2, 2)],
+        [(1, 1), (2, 1), (1, 2), (2, 2)],
+        [(1, 1), (2, 1), (1, 2), (2, 2)],
+        [(1, 1), (2, 1), (1, 2), (2, 2)],
+    ],
+    // Tミノ
+    [
+        [(0, 1), (1, 1), (2, 1), (1, 2)],
+        [(1, 0), (0, 1), (1, 1), (1, 2)],
+        [(1, 1), (0, 2), (1, 2), (2, 2)],
+        [(1, 0), (1, 1), (2, 1), (1, 2)],
+    ],
+    // Sミノ（2状態）
+    [
+        [(1, 1), (2, 1), (0, 2), (1, 2)],
+        [(0, 0), (0, 1), (1, 1), (1, 2)],
+        [(1, 1), (2, 1), (0, 2), (1, 2)],
+        [(0, 0), (0, 1), (1, 1), (1, 2)],
+    ],
+    // Zミノ（2状態）
+    [
+        [(0, 1), (1, 1), (1, 2), (2, 2)],
+        [(2, 0), (1, 1), (2, 1), (1, 2)],
+        [(0, 1), (1, 1), (1, 2), (2, 2)],
+        [(2, 0), (1, 1), (2, 1), (1, 2)],
+    ],
+    // Jミノ
+    [
+        [(0, 1), (1, 1), (2, 1), (2, 2)],
+        [(1, 0), (1, 1), (0, 2), (1, 2)],
+        [(0, 1), (0, 2), (1, 2), (2, 2)],
+        [(1, 0), (2, 0), (1, 1), (1, 2)],
+    ],
+    // Lミノ
+    [
+        [(0, 1), (1, 1), (2, 1), (0, 2)],
+        [(0, 0), (1, 0), (1, 1), (1, 2)],
+        [(2, 1), (0, 2), (1, 2), (2, 2)],
+        [(1, 0), (1, 1), (1, 2), (2, 2)],
+    ],
+];
+
+// Arika式回転法則（TGM）
+// 壁蹴りはその場→右→左の3通りのみ。Iミノは壁蹴りしない
+// T/J/Lは、回転後の位置で最初にぶつかるマス（上の行から左→右の順）が
+// 中央列なら壁蹴りしない（中央列ルール）
+pub struct ArikaRotationSystem;
+
+impl RotationSystem for ArikaRotationSystem {
+    fn shape(&self, kind: TetrominoKind, rotation: u8) -> &'static [(i32, i32); 4] {
+        shape_of(&ARS_SHAPES, kind, rotation)
+    }
+
+    fn kicks(&self, kind: TetrominoKind, _from: u8, _to: u8) -> &'static [KickOffset] {
+        match kind {
+            TetrominoKind::I | TetrominoKind::O => NO_KICK,
+            _ => &[KickOffset {x:0, y:0}, KickOffset {x:1, y:0}, KickOffset {x:-1, y:0}],
+        }
+    }
+
+    fn allows_kick(&self, rotated: &Tetromino, field: &dyn Occupancy) -> bool {
+        if !matches!(rotated.kind, TetrominoKind::T | TetrominoKind::J | TetrominoKind::L) {
+            return true;
+        }
+        let mut blocks = *self.shape(rotated.kind, rotated.rotation);
+        blocks.sort_by_key(|&(dx, dy)| (dy, dx));
+        match blocks.iter().find(|&&(dx, dy)| field.is_filled(rotated.x + dx, rotated.y + dy)) {
+            Some(&(dx, _)) => dx != 1,
+            None => true,
+        }
+    }
+}
+
+// クラシック（NES）のI/S/Zは2状態
+static NRS_I_SHAPES: ShapeRotations = [
+    [(0, 2), (1, 2), (2, 2), (3, 2)],
+    [(2, 0), (2, 1), (2, 2), (2, 3)],
+    [(0, 2), (1, 2), (2, 2), (3, 2)],
+    [(2, 0), (2, 1), (2, 2), (2, 3)],
+];
+static NRS_S_SHAPES: ShapeRotations = [
+    [(1, 1), (2, 1), (0, 2), (1, 2)],
+    [(1, 0), (1, 1), (2, 1), (2, 2)],
+    [(1, 1), (2, 1), (0, 2), (1, 2)],
+    [(1, 0), (1, 1), (2, 1), (2, 2)],
+];
+static NRS_Z_SHAPES: ShapeRotations = [
+    [(0, 1), (1, 1), (1, 2), (2, 2)],
+    [(2, 0), (1, 1), (2, 1), (1, 2)],
+    [(0, 1), (1, 1), (1, 2), (2, 2)],
+    [(2, 0), (1, 1), (2, 1), (1, 2)],
+];
+
+// 任天堂式回転法則（クラシック）
+// 壁蹴りなし。T/J/L/OはSRSと同じ形状で、T/J/Lは平らな面を上にして出現する
+pub struct NintendoRotationSystem;
+
+impl RotationSystem for NintendoRotationSystem {
+    fn shape(&self, kind: TetrominoKind, rotation: u8) -> &'static [(i32, i32); 4] {
+        let rotation = (rotation % 4) as usize;
+        match kind {
+            TetrominoKind::I => &NRS_I_SHAPES[rotation],
+            TetrominoKind::S => &NRS_S_SHAPES[rotation],
+            TetrominoKind::Z => &NRS_Z_SHAPES[rotation],
+            _ => &SRS_SHAPES[kind.index()][rotation],
+        }
+    }
+
+    fn spawn_rotation(&self, kind: TetrominoKind) -> u8 {
+        match kind {
+            TetrominoKind::T | TetrominoKind::J | TetrominoKind::L => 2,
+            _ => 0,
+        }
+    }
+
+    fn kicks(&self, _kind: TetrominoKind, _from: u8, _to: u8) -> &'static [KickOffset] {
+        NO_KICK
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Field;
+
+    fn empty_field() -> Field {
+        Field { width: 10, height: 20, cells: vec![0; 200] }
+    }
+
+    #[test]
+    fn test_parse_rotation_system() {
+        assert_eq!(RotationSystemKind::parse("srs"), Some(RotationSystemKind::Srs));
+        assert_eq!(RotationSystemKind::parse("tgm"), Some(RotationSystemKind::Ars));
+        assert_eq!(RotationSystemKind::parse("classic"), Some(RotationSystemKind::Nrs));
+        assert_eq!(RotationSystemKind::parse("unknown"), None);
+    }
+
+    #[test]
+    fn test_every_system_shapes_fit_in_4x4() {
+        for kind in [RotationSystemKind::Srs, RotationSystemKind::Ars, RotationSystemKind::Nrs] {
+            let system = kind.system();
+            for piece in TetrominoKind::ALL {
+                for rotation in 0..4 {
+                    let shape = system.shape(piece, rotation);
+                    assert!(shape.iter().all(|&(x, y)| (0..4).contains(&x) && (0..4).contains(&y)), "{:?} {} {}", kind, piece, rotation);
+                }
+                assert_eq!(system.kicks(piece, 0, 1)[0], KickOffset { x: 0, y: 0 });
+            }
+        }
+    }
+
+    #[test]
+    fn test_srs_kicks_are_static() {
+        let system = RotationSystemKind::Srs.system();
+        assert_eq!(system.kicks(TetrominoKind::T, 0, 1).len(), 5);
+        assert_eq!(system.kicks(TetrominoKind::I, 0, 1)[1], KickOffset { x: -2, y: 0 });
+        assert_eq!(system.kicks(TetrominoKind::T, 0, 2).len(), 6);
+        assert_eq!(system.kicks(TetrominoKind::O, 0, 1).len(), 1);
+    }
+
+    #[test]
+    fn test_corner_center_follows_shape() {
+        let srs = RotationSystemKind::Srs.system();
+        let ars = RotationSystemKind::Ars.system();
+        let nrs = RotationSystemKind::Nrs.system();
+        // 形状から求めた中心はSRSの出現時・右向きの中心と一致する
+        for piece in [TetrominoKind::T, TetrominoKind::S, TetrominoKind::Z, TetrominoKind::J, TetrominoKind::L] {
+            for rotation in 0..2 {
+                assert_eq!(shape_center(srs.shape(piece, rotation)), (1, 1), "{} {}", piece, rotation);
+            }
+            assert_eq!(srs.corner_center(piece, 2), (1, 1));
+        }
+        // ARSのT/J/Lは平らな面が下の向きで1段下、S/Zは下詰め・左詰め
+        for piece in [TetrominoKind::T, TetrominoKind::J, TetrominoKind::L] {
+            assert_eq!(ars.corner_center(piece, 0), (1, 1), "{}", piece);
+            assert_eq!(ars.corner_center(piece, 2), (1, 2), "{}", piece);
+        }
+        assert_eq!(ars.corner_center(TetrominoKind::S, 0), (1, 2));
+        assert_eq!(ars.corner_center(TetrominoKind::S, 1), (0, 1));
+        assert_eq!(ars.corner_center(TetrominoKind::Z, 1), (1, 1));
+        // NRSのS/Zは横向きが下詰め
+        assert_eq!(nrs.corner_center(TetrominoKind::S, 0), (1, 2));
+        assert_eq!(nrs.corner_center(TetrominoKind::Z, 0), (1, 2));
+        assert_eq!(nrs.corner_center(TetrominoKind::S, 1), (1, 1));
+        assert_eq!(nrs.corner_center(TetrominoKind::J, 2), (1, 1));
+    }
+
+    #[test]
+    fn test_ars_center_column_rule() {
+        let system = ArikaRotationSystem;
+        // 回転後のTミノ(1)の中央列上端が塞がっている → 壁蹴りしない
+        let mut field = empty_field();
+        field.cells[5 * 10 + 5] = 1;
+        let rotated = Tetromino { kind: TetrominoKind::T, x: 4, y: 5, rotation: 1 };
+        assert!(!system.allows_kick(&rotated, &field));
+        // 左列が最初にぶつかる → 壁蹴りする
+        let mut field = empty_field();
+        field.cells[6 * 10 + 4] = 1;
+        assert!(system.allows_kick(&rotated, &field));
+        // Iミノは中央列ルールの対象外だが、そもそも壁蹴りしない
+        assert_eq!(system.kicks(TetrominoKind::I, 0, 1).len(), 1);
+    }
+
+    #[test]
+    fn test_spawn_position_is_centered_for_any_width() {
+        let srs = RotationSystemKind::Srs.system();
+        // 10列: 3マス幅のミノは3〜5列、I/Oは中央
+        assert_eq!(srs.spawn_position(TetrominoKind::T, 10, 20), (3, 18));
+        assert_eq!(srs.spawn_position(TetrominoKind::I, 10, 20), (3, 18));
+        assert_eq!(srs.spawn_position(TetrominoKind::O, 10, 20), (3, 18));
+        // 4列・12列
+        assert_eq!(srs.spawn_position(TetrominoKind::T, 4, 20).0, 0);
+        assert_eq!(srs.spawn_position(TetrominoKind::I, 4, 20).0, 0);
+        assert_eq!(srs.spawn_position(TetrominoKind::T, 12, 20).0, 4);
+        assert_eq!(srs.spawn_position(TetrominoKind::O, 12, 20).0, 4);
+        // バッファなしでは最上段がy=0
+        assert_eq!(srs.spawn_position(TetrominoKind::T, 10, 0).1, 0);
+        assert_eq!(srs.spawn_position(TetrominoKind::I, 10, 0).1, -1);
+        // ARSは下詰めの形状なので1段上にずらす
+        let ars = RotationSystemKind::Ars.system();
+        assert_eq!(ars.spawn_position(TetrominoKind::T, 10, 20), (3, 17));
+    }
+
+    #[test]
+    fn test_nrs_spawn_orientation() {
+        let system = NintendoRotationSystem;
+        assert_eq!(system.spawn_rotation(TetrominoKind::T), 2);
+        assert_eq!(system.spawn_rotation(TetrominoKind::I), 0);
+        assert_eq!(system.shape(TetrominoKind::S, 0), system.shape(TetrominoKind::S, 2));
+        assert_eq!(system.kicks(TetrominoKind::T, 0, 1).len(), 1);
+    }
+}
@@ -0,0 +1,300 @@
+use crate::combo::{ComboState, B2B_MULTIPLIER_DEN, B2B_MULTIPLIER_NUM, COMBO_BONUS};
+use crate::{SpinResult, SpinType, SpinVariant};
+use serde::{Deserialize, Serialize};
+
+pub const PLACEMENT_SCORE: u32 = 10;
+pub const HARD_DROP_PER_ROW: u32 = 1;
+
+// ライン消去の基本点（1〜4ライン）
+pub fn line_clear_score(lines: u32) -> u32 {
+    match lines {
+        1 => 100,
+        2 => 300,
+        3 => 500,
+        4 => 800,
+        _ => 0,
+    }
+}
+
+// パーフェクトクリア（全消し）ボーナス。B2Bテトリスでの全消しは3200点
+pub fn perfect_clear_bonus(lines: u32, back_to_back: bool) -> u32 {
+    match lines {
+        1 => 800,
+        2 => 1200,
+        3 => 1800,
+        4 if back_to_back => 3200,
+        4 => 2000,
+        _ => 0,
+    }
+}
+
+// 消去の種類（実績判定や対戦の攻撃テーブル用）
+// 全消しは他の種類より優先してPerfectClearとする
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ClearType {
+    #[default]
+    None,
+    Single,
+    Double,
+    Triple,
+    Tetris,
+    SpinMini,
+    SpinSingle,
+    SpinDouble,
+    SpinTriple,
+    PerfectClear,
+}
+
+impl ClearType {
+    pub fn classify(lines: u32, spin: &SpinResult, perfect_clear: bool) -> Self {
+        if perfect_clear && lines > 0 {
+            return ClearType::PerfectClear;
+        }
+        if spin.spin_type != SpinType::None && lines > 0 {
+            if spin.variant == SpinVariant::Mini {
+                return ClearType::SpinMini;
+            }
+            return match lines {
+                1 => ClearType::SpinSingle,
+                2 => ClearType::SpinDouble,
+                _ => ClearType::SpinTriple,
+            };
+        }
+        match lines {
+            1 => ClearType::Single,
+            2 => ClearType::Double,
+            3 => ClearType::Triple,
+            4 => ClearType::Tetris,
+            _ => ClearType::None,
+        }
+    }
+}
+
+// 1回の固定で得たスコアの内訳（UIのポップアップ表示用）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScoreAward {
+    pub placement: u32,
+    pub line_clear: u32,
+    pub spin_bonus: u32,
+    pub soft_drop: u32,
+    pub hard_drop: u32,
+    pub combo_bonus: u32,
+    pub b2b_bonus: u32,
+    pub perfect_clear_bonus: u32,
+    // フィーバー中の倍率（通常は1）
+    pub multiplier: u32,
+    pub total: u32,
+    pub lines: u32,
+    pub clear_type: ClearType,
+    pub spin_type: Option<SpinType>,
+    pub spin_variant: Option<SpinVariant>,
+    pub combo: u32,
+    pub back_to_back: bool,
+}
+
+// スコアの集計
+// ドロップ点はミノ操作中に溜めておき、固定時にまとめて加算する
+#[derive(Default)]
+pub struct Scoring {
+    score: u32,
+    soft_drop_rows: u32,
+    hard_drop_rows: u32,
+    // ソフトドロップは0.5点/行なので奇数行の端数を持ち越す
+    soft_drop_carry: u32,
+    last_award: Option<ScoreAward>,
+    combo: ComboState,
+    blocks_placed: u32,
+    tetris_count: u32,
+    tspin_count: u32,
+    perfect_clear_count: u32,
+}
+
+impl Scoring {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    pub fn last_award(&self) -> Option<&ScoreAward> {
+        self.last_award.as_ref()
+    }
+
+    pub fn combo(&self) -> &ComboState {
+        &self.combo
+    }
+
+    pub fn blocks_placed(&self) -> u32 {
+        self.blocks_placed
+    }
+
+    pub fn tetris_count(&self) -> u32 {
+        self.tetris_count
+    }
+
+    pub fn tspin_count(&self) -> u32 {
+        self.tspin_count
+    }
+
+    pub fn perfect_clear_count(&self) -> u32 {
+        self.perfect_clear_count
+    }
+
+    pub fn add_soft_drop(&mut self, rows: u32) {
+        self.soft_drop_rows += rows;
+    }
+
+    pub fn add_hard_drop(&mut self, rows: u32) {
+        self.hard_drop_rows += rows;
+    }
+
+    // 固定時のスコアを計算して加算する
+    // perfect_clear: 消去後のフィールドが空になったか
+    // multiplier: 合計に掛ける倍率（フィーバー）
+    pub fn award_lock(&mut self, lines: u32, spin: &SpinResult, perfect_clear: bool, multiplier: u32) -> ScoreAward {
+        let perfect_clear = perfect_clear && lines > 0;
+        let half_rows = self.soft_drop_carry + self.soft_drop_rows;
+        self.soft_drop_carry = half_rows % 2;
+        let is_spin = spin.spin_type != SpinType::None;
+        let difficult = lines == 4 || (is_spin && lines > 0);
+        let update = self.combo.on_lock(lines, difficult);
+
+        let mut award = ScoreAward {
+            placement: PLACEMENT_SCORE,
+            line_clear: line_clear_score(lines),
+            spin_bonus: if is_spin { spin.bonus } else { 0 },
+            soft_drop: half_rows / 2,
+            hard_drop: self.hard_drop_rows * HARD_DROP_PER_ROW,
+            combo_bonus: COMBO_BONUS * update.combo,
+            b2b_bonus: 0,
+            perfect_clear_bonus: if perfect_clear { perfect_clear_bonus(lines, update.back_to_back) } else { 0 },
+            multiplier,
+            total: 0,
+            lines,
+            clear_type: ClearType::classify(lines, spin, perfect_clear),
+            spin_type: is_spin.then_some(spin.spin_type),
+            spin_variant: is_spin.then_some(spin.variant),
+            combo: update.combo,
+            back_to_back: update.back_to_back,
+        };
+        if update.back_to_back {
+            let base = award.line_clear + award.spin_bonus;
+            award.b2b_bonus = base * B2B_MULTIPLIER_NUM / B2B_MULTIPLIER_DEN - base;
+        }
+        award.total = (award.placement + award.line_clear + award.spin_bonus + award.soft_drop + award.hard_drop
+            + award.combo_bonus + award.b2b_bonus + award.perfect_clear_bonus) * multiplier;
+
+        self.score = self.score.saturating_add(award.total);
+        self.soft_drop_rows = 0;
+        self.hard_drop_rows = 0;
+        self.blocks_placed += 1;
+        if lines == 4 {
+            self.tetris_count += 1;
+        }
+        if spin.spin_type == SpinType::TSpin {
+            self.tspin_count += 1;
+        }
+        if perfect_clear {
+            self.perfect_clear_count += 1;
+        }
+        self.last_award = Some(award.clone());
+        award
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_spin() -> SpinResult {
+        SpinResult { spin_type: SpinType::None, variant: SpinVariant::None, bonus: 0, lines: 0 }
+    }
+
+    #[test]
+    fn test_line_clear_table() {
+        let mut scoring = Scoring::new();
+        let award = scoring.award_lock(4, &no_spin(), false, 1);
+        assert_eq!(award.line_clear, 800);
+        assert_eq!(award.placement, PLACEMENT_SCORE);
+        assert_eq!(award.total, 810);
+        assert_eq!(scoring.score(), 810);
+    }
+
+    #[test]
+    fn test_drop_points() {
+        let mut scoring = Scoring::new();
+        scoring.add_soft_drop(3);
+        let award = scoring.award_lock(0, &no_spin(), false, 1);
+        assert_eq!(award.soft_drop, 1);
+        // 前回の端数0.5点が持ち越される
+        scoring.add_soft_drop(1);
+        scoring.add_hard_drop(12);
+        let award = scoring.award_lock(0, &no_spin(), false, 1);
+        assert_eq!(award.soft_drop, 1);
+        assert_eq!(award.hard_drop, 12);
+        assert_eq!(scoring.score(), 10 + 1 + 10 + 1 + 12);
+    }
+
+    #[test]
+    fn test_spin_bonus_is_added() {
+        let mut scoring = Scoring::new();
+        let tsd = SpinResult { spin_type: SpinType::TSpin, variant: SpinVariant::Double, bonus: 5000, lines: 2 };
+        let award = scoring.award_lock(2, &tsd, false, 1);
+        assert_eq!(award.spin_bonus, 5000);
+        assert_eq!(award.line_clear, 300);
+        assert_eq!(award.spin_type, Some(SpinType::TSpin));
+        assert_eq!(award.total, 5310);
+    }
+
+    #[test]
+    fn test_combo_and_b2b_bonus() {
+        let mut scoring = Scoring::new();
+        scoring.award_lock(4, &no_spin(), false, 1);
+        let award = scoring.award_lock(4, &no_spin(), false, 1);
+        assert!(award.back_to_back);
+        assert_eq!(award.combo, 1);
+        assert_eq!(award.combo_bonus, 50);
+        assert_eq!(award.b2b_bonus, 400);
+        assert_eq!(award.total, 10 + 800 + 50 + 400);
+        assert_eq!(scoring.tetris_count(), 2);
+        assert_eq!(scoring.combo().max_combo(), 1);
+    }
+
+    #[test]
+    fn test_perfect_clear_bonus() {
+        let mut scoring = Scoring::new();
+        let award = scoring.award_lock(4, &no_spin(), true, 1);
+        assert_eq!(award.clear_type, ClearType::PerfectClear);
+        assert_eq!(award.perfect_clear_bonus, 2000);
+        // B2Bテトリスでの全消し
+        let award = scoring.award_lock(4, &no_spin(), true, 1);
+        assert!(award.back_to_back);
+        assert_eq!(award.perfect_clear_bonus, 3200);
+        assert_eq!(scoring.perfect_clear_count(), 2);
+        // 消去なしでは全消し扱いにしない
+        let award = scoring.award_lock(0, &no_spin(), true, 1);
+        assert_eq!(award.clear_type, ClearType::None);
+        assert_eq!(award.perfect_clear_bonus, 0);
+    }
+
+    #[test]
+    fn test_clear_type_classification() {
+        let tsd = SpinResult { spin_type: SpinType::TSpin, variant: SpinVariant::Double, bonus: 5000, lines: 2 };
+        let mini = SpinResult { spin_type: SpinType::TSpin, variant: SpinVariant::Mini, bonus: 1000, lines: 1 };
+        assert_eq!(ClearType::classify(2, &tsd, false), ClearType::SpinDouble);
+        assert_eq!(ClearType::classify(1, &mini, false), ClearType::SpinMini);
+        assert_eq!(ClearType::classify(3, &no_spin(), false), ClearType::Triple);
+        assert_eq!(ClearType::classify(2, &tsd, true), ClearType::PerfectClear);
+    }
+
+    #[test]
+    fn test_multiplier_applies_to_total() {
+        let mut scoring = Scoring::new();
+        let award = scoring.award_lock(1, &no_spin(), false, 4);
+        assert_eq!(award.multiplier, 4);
+        assert_eq!(award.total, (10 + 100) * 4);
+        assert_eq!(scoring.score(), 440);
+    }
+}
@@ -0,0 +1,1490 @@
+use wasm_bindgen::prelude::*;
+use serde::{Serialize, Deserialize};
+use rand::Rng;
+
+use crate::achievement::GameStats;
+use crate::bitboard::MAX_WIDTH;
+use crate::cell;
+use crate::error::{to_js, EngineError};
+use crate::fever::{Fever, FeverEvent};
+use crate::gravity::Gravity;
+use crate::hold::{Hold, HoldRejection};
+use crate::lock_delay::LockDelay;
+use crate::points::{InsufficientPoints, PointsLedger, PointsSource, PointsTransaction, HOLD_COST, LINE_DELETE_COST};
+use crate::queue::{NextQueue, DEFAULT_PREVIEW};
+use crate::randomizer::RandomizerKind;
+use crate::rank::{DanRank, RankInfo, RankPromotion};
+use crate::rotation::{RotationSystem, RotationSystemKind};
+use crate::scoring::{ScoreAward, Scoring};
+use crate::{
+    attempt_rotation, check_collision, count_completed_lines, detect_spin_with_rule, detect_t_spin, drop_position,
+    fix_tetromino, is_grounded, is_lock_out,
+    BitBoard, Field, GameOverReason, MoveDirection, RotationDirection, RotationResult, SpinResult, SpinRule, TSpinType, Tetromino, TetrominoKind,
+    DEFAULT_BUFFER_ROWS,
+};
+
+// 1ゲーム分のエンジン状態（フィールド・操作中のミノ・スピン判定・スコアなど）
+// セッションごとに独立しているので、複数の盤面を同時に動かせる
+#[wasm_bindgen]
+pub struct GameSession {
+    // バッファを含めたフィールド全体（JSにはFieldに変換して渡す）
+    field: BitBoard,
+    buffer_rows: u32,
+    tetromino: Tetromino,
+    last_tspin: TSpinType,
+    last_wallkick: bool,
+    last_kick_index: i32,
+    // 最後に成功した操作が回転ならその方向（移動・落下でNone）
+    last_rotation: Option<RotationDirection>,
+    spin_rule: SpinRule,
+    // 操作中のミノがホールドから出したものか
+    active_from_hold: bool,
+    // 操作中のミノが固定時に自動で出したものか（直後のspawn_tetrominoで次を出さない）
+    spawned_on_lock: bool,
+    seed: u32,
+    next_queue: NextQueue,
+    hold: Hold,
+    // ホールド1回にポイント台帳から支払わせるポイント（0ならコストなし）
+    hold_point_cost: u32,
+    lock_delay: LockDelay,
+    gravity: Gravity,
+    game_over: Option<GameOverReason>,
+    scoring: Scoring,
+    dan_rank: DanRank,
+    fever: Fever,
+    points: PointsLedger,
+    rotation: RotationSystemKind,
+    // このセッションで始めたゲームの数（resetで数え、次のゲームにも引き継ぐ）
+    games_played: u32,
+    // 現在のゲームで進めた時間(ms)。tickの経過時間とupdateの1段分の時間を足す
+    play_time_ms: u64,
+}
+
+impl Default for GameSession {
+    // 可視10x20＋バッファ20段、ランダムなシードの7bag・SRS
+    fn default() -> Self {
+        let seed = rand::thread_rng().gen();
+        Self {
+            field: BitBoard::new(10, 20 + DEFAULT_BUFFER_ROWS),
+            buffer_rows: DEFAULT_BUFFER_ROWS,
+            tetromino: Tetromino {
+                kind: TetrominoKind::I,
+                x: 3,
+                y: DEFAULT_BUFFER_ROWS as i32 - 2,
+                rotation: 0,
+            },
+            last_tspin: TSpinType::None,
+            last_wallkick: false,
+            last_kick_index: 0,
+            last_rotation: None,
+            spin_rule: SpinRule::Corner,
+            active_from_hold: false,
+            spawned_on_lock: false,
+            seed,
+            next_queue: NextQueue::new(RandomizerKind::SevenBag, seed, DEFAULT_PREVIEW),
+            hold: Hold::new(),
+            hold_point_cost: 0,
+            lock_delay: LockDelay::default(),
+            gravity: Gravity::default(),
+            game_over: None,
+            scoring: Scoring::new(),
+            dan_rank: DanRank::default(),
+            fever: Fever::default(),
+            points: PointsLedger::default(),
+            rotation: RotationSystemKind::Srs,
+            games_played: 0,
+            play_time_ms: 0,
+        }
+    }
+}
+
+// JSから渡された名前を解釈する（省略時はNone、未知の名前はエラー）
+fn parse_name<T>(
+    name: Option<&str>,
+    what: &str,
+    parse: fn(&str) -> Option<T>,
+) -> Result<Option<T>, EngineError> {
+    match name {
+        Some(name) => parse(name)
+            .map(Some)
+            .ok_or_else(|| EngineError::InvalidInput(format!("未知の{}: {:?}", what, name))),
+        None => Ok(None),
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct HoldResult {
+    pub success: bool,
+    pub tetromino: Tetromino,
+    pub hold_piece: Option<TetrominoKind>,
+    pub reason: Option<HoldRejection>,
+    // ポイント消費型ホールドの場合の取引
+    pub transaction: Option<PointsTransaction>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PointsActionResult {
+    pub success: bool,
+    pub tetromino: Tetromino,
+    pub transaction: Option<PointsTransaction>,
+    pub error: Option<InsufficientPoints>,
+    pub game_over: bool,
+}
+
+impl PointsActionResult {
+    fn new(tetromino: Tetromino, paid: Result<PointsTransaction, InsufficientPoints>, game_over: bool) -> Self {
+        let success = paid.is_ok();
+        let (transaction, error) = match paid {
+            Ok(transaction) => (Some(transaction), None),
+            Err(error) => (None, Some(error)),
+        };
+        Self { success, tetromino, transaction, error, game_over }
+    }
+
+    fn rejected(tetromino: Tetromino, game_over: bool) -> Self {
+        Self {
+            success: false,
+            tetromino,
+            transaction: None,
+            error: None,
+            game_over,
+        }
+    }
+}
+
+struct LockOutcome {
+    spin: SpinResult,
+    perfect_clear: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MoveResult {
+    pub tetromino: Tetromino,
+    pub moved: bool,
+    // trueの場合、ミノは固定済みでtetrominoは次に出現したミノ
+    pub locked: bool,
+    pub game_over: bool,
+    // 固定時に得たスコアの内訳
+    pub award: Option<ScoreAward>,
+    // 固定によって段位が上がった場合の昇格イベント
+    pub promotion: Option<RankPromotion>,
+    // フィーバーの開始・終了
+    pub fever: Option<FeverEvent>,
+    // 固定で発生したポイント取引（配置・段位昇格ボーナス）
+    pub points: Vec<PointsTransaction>,
+}
+
+impl MoveResult {
+    fn new(tetromino: Tetromino, moved: bool, game_over: bool) -> Self {
+        Self {
+            tetromino,
+            moved,
+            locked: false,
+            game_over,
+            award: None,
+            promotion: None,
+            fever: None,
+            points: Vec::new(),
+        }
+    }
+
+    fn locked(lock: LockResult, moved: bool, game_over: bool) -> Self {
+        Self {
+            tetromino: lock.tetromino,
+            moved,
+            locked: true,
+            game_over,
+            award: Some(lock.award),
+            promotion: lock.promotion,
+            fever: lock.fever,
+            points: lock.points,
+        }
+    }
+}
+
+struct LockResult {
+    tetromino: Tetromino,
+    award: ScoreAward,
+    promotion: Option<RankPromotion>,
+    fever: Option<FeverEvent>,
+    points: Vec<PointsTransaction>,
+}
+
+impl GameSession {
+    // 新しいゲームを始める
+    // 開始レベル・固定猶予・ホールドのコスト・NEXTの表示数・スピン判定方式は引き継ぐ
+    fn reset(
+        &mut self,
+        width: u32,
+        height: u32,
+        seed: Option<u32>,
+        randomizer: Option<String>,
+        rotation: Option<String>,
+        buffer_rows: Option<u32>,
+    ) -> Result<(), EngineError> {
+        // 不正な指定では現在のゲームを変更しない
+        if !(1..=MAX_WIDTH).contains(&width) {
+            return Err(EngineError::InvalidInput(format!("フィールドの幅は1〜{}列: {}", MAX_WIDTH, width)));
+        }
+        let kind = parse_name(randomizer.as_deref(), "乱数器", RandomizerKind::parse)?
+            .unwrap_or(RandomizerKind::SevenBag);
+        self.rotation = parse_name(rotation.as_deref(), "回転法則", RotationSystemKind::parse)?
+            .unwrap_or(RotationSystemKind::Srs);
+        self.reset_randomizer(seed.unwrap_or_else(|| rand::thread_rng().gen()), kind);
+        self.hold.reset();
+        self.spawned_on_lock = false;
+        self.game_over = None;
+        self.scoring = Scoring::new();
+        self.dan_rank = DanRank::default();
+        self.fever = Fever::default();
+        self.points = PointsLedger::default();
+        self.gravity = Gravity::new(self.gravity.start_level());
+        self.buffer_rows = buffer_rows.unwrap_or(DEFAULT_BUFFER_ROWS);
+        self.field = BitBoard::new(width, height + self.buffer_rows);
+        self.play_time_ms = 0;
+        self.games_played += 1;
+        Ok(())
+    }
+
+    pub(crate) fn try_new(
+        width: u32,
+        height: u32,
+        seed: Option<u32>,
+        randomizer: Option<String>,
+        rotation: Option<String>,
+        buffer_rows: Option<u32>,
+    ) -> Result<GameSession, EngineError> {
+        let mut session = GameSession::default();
+        session.reset(width, height, seed, randomizer, rotation, buffer_rows)?;
+        Ok(session)
+    }
+
+    // ゲーム開始時に選択された回転法則
+    pub(crate) fn current_tetromino(&self) -> &Tetromino {
+        &self.tetromino
+    }
+
+    pub(crate) fn hold_piece(&self) -> Option<TetrominoKind> {
+        self.hold.piece()
+    }
+
+    pub(crate) fn system(&self) -> &'static dyn RotationSystem {
+        self.rotation.system()
+    }
+
+    // 可視領域の最上段
+    fn visible_top(&self) -> i32 {
+        self.buffer_rows as i32
+    }
+
+    pub(crate) fn visible_field(&self) -> Field {
+        self.field.visible_part(self.buffer_rows)
+    }
+
+    pub(crate) fn next_tetromino_kind(&mut self) -> TetrominoKind {
+        self.next_queue.pop()
+    }
+
+    // シードと乱数器を差し替える（同じシード・方式なら同じミノ順になる）
+    // NEXTの表示数は引き継ぐ
+    fn reset_randomizer(&mut self, seed: u32, kind: RandomizerKind) {
+        self.seed = seed;
+        self.next_queue = NextQueue::new(kind, seed, self.next_queue.preview_len());
+    }
+
+    pub(crate) fn next_queue_kinds(&self) -> Vec<TetrominoKind> {
+        self.next_queue.preview()
+    }
+
+    // 指定ミノを出現位置に置き、回転・固定猶予の状態をリセット
+    // last_tspinは直前の固定結果として次の固定まで保持する
+    // 出現位置が埋まっていればブロックアウト
+    pub(crate) fn spawn_piece(&mut self, kind: TetrominoKind) -> Tetromino {
+        // 可視領域のすぐ上（ガイドラインの21〜22段目）の中央に出現
+        let system = self.system();
+        let (x, y) = system.spawn_position(kind, self.field.width(), self.visible_top());
+        let tetromino = Tetromino {
+            rotation: system.spawn_rotation(kind),
+            kind,
+            x,
+            y,
+        };
+        self.tetromino = tetromino.clone();
+        self.last_wallkick = false;
+        self.last_kick_index = 0;
+        self.last_rotation = None;
+        self.active_from_hold = false;
+        self.spawned_on_lock = false;
+        self.lock_delay.start(tetromino.y);
+        if check_collision(system, &self.field, &tetromino) {
+            self.game_over = Some(GameOverReason::BlockOut);
+        }
+        tetromino
+    }
+
+    // 操作中のミノをホールドし、ホールド枠（空ならNEXT）のミノを出す
+    pub(crate) fn hold_current(&mut self) -> HoldResult {
+        let current = self.tetromino.clone();
+        let history_len = self.points.history().len();
+        let held = if self.is_game_over() {
+            Err(HoldRejection::GameOver)
+        } else if self.hold_point_cost > 0 && self.hold.can_hold() {
+            // 台帳から支払えた場合だけホールドする（フィーバー中は無料）
+            let fever = self.fever.is_active();
+            match self.points.spend(PointsSource::Hold, self.hold_point_cost, fever) {
+                Ok(_) => self.hold.hold(current.kind),
+                Err(_) => Err(HoldRejection::CostNotPaid),
+            }
+        } else {
+            self.hold.hold(current.kind)
+        };
+        let (tetromino, reason) = match held {
+            Ok(Some(kind)) => {
+                let tetromino = self.spawn_piece(kind);
+                self.active_from_hold = true;
+                (tetromino, None)
+            }
+            Ok(None) => {
+                let kind = self.next_tetromino_kind();
+                (self.spawn_piece(kind), None)
+            }
+            Err(reason) => (current, Some(reason)),
+        };
+        HoldResult {
+            success: reason.is_none(),
+            tetromino,
+            hold_piece: self.hold.piece(),
+            reason,
+            transaction: self.points.history().get(history_len).cloned(),
+        }
+    }
+
+    // 次のホールドで実際に支払うコスト（フィーバー中は0）
+    fn hold_cost(&self) -> u32 {
+        if self.fever.is_active() { 0 } else { self.hold_point_cost }
+    }
+
+    // 操作中のミノを捨ててNEXTの先頭と入れ替える（累積コスト、フィーバー中は無料）
+    fn exchange_active(&mut self) -> PointsActionResult {
+        if self.is_game_over() {
+            return PointsActionResult::rejected(self.tetromino.clone(), true);
+        }
+        let fever = self.fever.is_active();
+        let paid = self.points.spend_exchange(fever);
+        let tetromino = if paid.is_ok() {
+            let kind = self.next_tetromino_kind();
+            self.spawn_piece(kind)
+        } else {
+            self.tetromino.clone()
+        };
+        PointsActionResult::new(tetromino, paid, self.is_game_over())
+    }
+
+    // 緊急ライン削除（200ポイント、フィーバー中は無料）
+    fn emergency_delete_active(&mut self) -> PointsActionResult {
+        if self.is_game_over() {
+            return PointsActionResult::rejected(self.tetromino.clone(), true);
+        }
+        let fever = self.fever.is_active();
+        let paid = self.points.spend(PointsSource::LineDelete, LINE_DELETE_COST, fever);
+        if paid.is_ok() {
+            self.field.delete_bottom_row();
+            // 下ろしたブロックが操作中のミノに重なった場合は押し上げる
+            let system = self.system();
+            while check_collision(system, &self.field, &self.tetromino) && self.tetromino.y > -(self.field.height() as i32) {
+                self.tetromino.y -= 1;
+            }
+        }
+        PointsActionResult::new(self.tetromino.clone(), paid, self.is_game_over())
+    }
+
+    // スピン判定は固定前のフィールドで行う
+    fn fix_tetromino_with_tspin(&mut self, tetromino: &Tetromino) -> LockOutcome {
+        let system = self.system();
+        let (wallkick, kick_index) = (self.last_wallkick, self.last_kick_index);
+        let rotation = self.last_rotation;
+        self.last_tspin = detect_t_spin(system, tetromino, &self.field, wallkick, kick_index, rotation);
+        let lines = count_completed_lines(system, &self.field, tetromino);
+        let spin = detect_spin_with_rule(
+            system,
+            tetromino,
+            &self.field,
+            wallkick,
+            kick_index,
+            lines,
+            self.spin_rule,
+            rotation,
+        );
+        let flags = if self.active_from_hold { cell::FLAG_FROM_HOLD } else { 0 };
+        fix_tetromino(system, &mut self.field, tetromino, flags);
+        self.field.clear_full_rows();
+        self.hold.on_lock();
+        LockOutcome {
+            spin,
+            perfect_clear: self.field.is_empty(),
+        }
+    }
+
+    // 操作中のミノを固定してスコアを加算し、次のミノを出す
+    // ロックアウトした場合は次のミノを出さずに固定したミノを返す
+    fn lock_active_piece(&mut self) -> LockResult {
+        let tetro = self.tetromino.clone();
+        let lock_out = is_lock_out(self.system(), &tetro, self.visible_top());
+        let outcome = self.fix_tetromino_with_tspin(&tetro);
+        let lines = outcome.spin.lines;
+        self.gravity.add_lines(lines);
+        // 倍率は固定前のフィーバー状態で決まる（発動させた配置自体は等倍）
+        let multiplier = self.fever.multiplier();
+        let award = self.scoring.award_lock(lines, &outcome.spin, outcome.perfect_clear, multiplier);
+        let promotion = self.dan_rank.update(self.scoring.score());
+        let fever = self.fever.on_placement();
+        let mut points = vec![self.points.on_placement()];
+        if let Some(promotion) = &promotion {
+            points.push(self.points.earn(PointsSource::RankBonus, promotion.bonus_points));
+        }
+        if lock_out {
+            self.game_over = Some(GameOverReason::LockOut);
+            return LockResult { tetromino: tetro, award, promotion, fever, points };
+        }
+        let kind = self.next_tetromino_kind();
+        let tetromino = self.spawn_piece(kind);
+        self.spawned_on_lock = true;
+        LockResult { tetromino, award, promotion, fever, points }
+    }
+
+    // 次のミノを出す。固定時に自動で出たミノがあればそれを返す（NEXTを1つ読み飛ばさない）
+    pub(crate) fn spawn_next_piece(&mut self) -> Tetromino {
+        if self.spawned_on_lock {
+            self.spawned_on_lock = false;
+            return self.tetromino.clone();
+        }
+        let kind = self.next_tetromino_kind();
+        self.spawn_piece(kind)
+    }
+
+    // 固定猶予を進め、必要なら固定する
+    fn advance_lock_delay(&mut self, dt_ms: u32) -> MoveResult {
+        let grounded = is_grounded(self.system(), &self.field, &self.tetromino);
+        if self.lock_delay.advance(dt_ms, grounded) {
+            let lock = self.lock_active_piece();
+            MoveResult::locked(lock, false, self.is_game_over())
+        } else {
+            MoveResult::new(self.tetromino.clone(), false, self.is_game_over())
+        }
+    }
+
+    // ゲームオーバー後の入力は受け付けない
+    fn rejected_move(&self) -> MoveResult {
+        MoveResult::new(self.tetromino.clone(), false, self.is_game_over())
+    }
+
+    pub(crate) fn move_active(&mut self, direction: MoveDirection) -> MoveResult {
+        if self.is_game_over() {
+            return self.rejected_move();
+        }
+        let mut tetro = self.tetromino.clone();
+        let (dx, dy) = direction.offset();
+        tetro.x += dx;
+        tetro.y += dy;
+        // 下方向の衝突でも即固定はせず、固定猶予に任せる
+        let moved = !check_collision(self.system(), &self.field, &tetro);
+        if moved {
+            self.lock_delay.on_move(tetro.y);
+            self.tetromino = tetro;
+            self.last_rotation = None;
+            if direction == MoveDirection::Down {
+                self.scoring.add_soft_drop(1);
+            }
+        }
+        // リセット上限に達した状態で接地していればここで固定される
+        let mut result = self.advance_lock_delay(0);
+        result.moved = moved;
+        result
+    }
+
+    // 自然落下を適用してから固定猶予を進める
+    pub(crate) fn tick_active(&mut self, dt_ms: u32) -> MoveResult {
+        if self.is_game_over() {
+            return self.rejected_move();
+        }
+        self.play_time_ms += dt_ms as u64;
+        let fever_event = self.fever.advance(dt_ms);
+        let rows = self.gravity.advance(dt_ms);
+        let system = self.system();
+        let mut tetro = self.tetromino.clone();
+        let mut moved = false;
+        for _ in 0..rows {
+            if is_grounded(system, &self.field, &tetro) {
+                break;
+            }
+            tetro.y += 1;
+            moved = true;
+        }
+        if is_grounded(system, &self.field, &tetro) {
+            self.gravity.reset_accumulator();
+        }
+        if moved {
+            self.lock_delay.on_fall(tetro.y);
+            self.tetromino = tetro;
+            self.last_rotation = None;
+        }
+        let mut result = self.advance_lock_delay(dt_ms);
+        result.moved = moved;
+        result.fever = result.fever.or(fever_event);
+        result
+    }
+
+    pub(crate) fn rotate_active(&mut self, direction: RotationDirection) -> RotationResult {
+        if self.is_game_over() {
+            return RotationResult { tetromino: self.tetromino.clone(), wallkick: false, kick_index: -1 };
+        }
+        let rot_result = attempt_rotation(self.system(), &self.tetromino, &self.field, direction);
+
+        // 回転できた場合だけwallkick情報を保存（失敗した回転で直前のキックを消さない）
+        if rot_result.kick_index >= 0 {
+            self.last_wallkick = rot_result.wallkick;
+            self.last_kick_index = rot_result.kick_index;
+            self.last_rotation = Some(direction);
+            self.lock_delay.on_move(rot_result.tetromino.y);
+        }
+        self.tetromino = rot_result.tetromino.clone();
+        rot_result
+    }
+
+    // 1段分の自然落下。接地していれば固定猶予を待たずに固定して次のミノを出す
+    pub(crate) fn gravity_step(&mut self) -> MoveResult {
+        if self.is_game_over() {
+            return self.rejected_move();
+        }
+        // 1回のupdateは1段落ちる時間が過ぎたものとして扱う
+        let row_interval_ms = self.gravity.row_interval_ms();
+        self.play_time_ms += row_interval_ms as u64;
+        let mut tetro = self.tetromino.clone();
+        tetro.y += 1;
+        let moved = !check_collision(self.system(), &self.field, &tetro);
+        if moved {
+            self.lock_delay.on_fall(tetro.y);
+            self.tetromino = tetro;
+            self.last_rotation = None;
+        }
+        // 接地したまま過ぎた1段分の時間だけ固定猶予を消費する（tickと同じ固定猶予で固定）
+        let dt_ms = if moved { 0 } else { row_interval_ms };
+        let mut result = self.advance_lock_delay(dt_ms);
+        result.moved = moved;
+        result
+    }
+
+    // JSからの操作はゲームオーバー後はエラーにする
+    pub(crate) fn ensure_playing(&self) -> Result<(), EngineError> {
+        if self.is_game_over() {
+            return Err(EngineError::GameOver);
+        }
+        Ok(())
+    }
+
+    // JSからの移動・回転の入力（未知の方向はエラー）
+    fn try_move(&mut self, dir: &str) -> Result<MoveResult, EngineError> {
+        let direction = MoveDirection::parse(dir)?;
+        self.ensure_playing()?;
+        Ok(self.move_active(direction))
+    }
+
+    fn try_rotate(&mut self, dir: &str) -> Result<RotationResult, EngineError> {
+        let direction = RotationDirection::parse(dir)?;
+        self.ensure_playing()?;
+        Ok(self.rotate_active(direction))
+    }
+
+    fn try_set_spin_rule(&mut self, rule: &str) -> Result<(), EngineError> {
+        self.spin_rule = parse_name(Some(rule), "スピン判定の方式", SpinRule::parse)?
+            .unwrap_or(SpinRule::Corner);
+        Ok(())
+    }
+
+    fn ghost_tetromino(&self) -> Tetromino {
+        drop_position(self.system(), &self.field, &self.tetromino)
+    }
+
+    pub(crate) fn hard_drop_active(&mut self) -> MoveResult {
+        if self.is_game_over() {
+            return self.rejected_move();
+        }
+        let start_y = self.tetromino.y;
+        let tetro = self.ghost_tetromino();
+        let rows = (tetro.y - start_y).max(0) as u32;
+        self.tetromino = tetro;
+        if rows > 0 {
+            self.last_rotation = None;
+        }
+        self.scoring.add_hard_drop(rows);
+        let lock = self.lock_active_piece();
+        MoveResult::locked(lock, rows > 0, self.is_game_over())
+    }
+
+    // 実績判定用の統計（AchievementManager.check_achievementsにそのまま渡せる）
+    fn game_stats(&self) -> GameStats {
+        let scoring = &self.scoring;
+        GameStats {
+            score: scoring.score(),
+            lines_cleared: self.gravity.lines(),
+            blocks_placed: scoring.blocks_placed(),
+            tetris_count: scoring.tetris_count(),
+            tspin_count: scoring.tspin_count(),
+            max_combo: scoring.combo().max_combo(),
+            perfect_clear_count: scoring.perfect_clear_count(),
+            hold_count: self.hold.count(),
+            level: self.gravity.level(),
+            fever_count: self.fever.count(),
+            exchange_count: self.points.total_exchanges(),
+            dan_rank: self.get_rank() as u32,
+            play_time: (self.play_time_ms / 1000) as u32,
+            games_played: self.games_played,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl GameSession {
+    // 引数はinit_fieldと同じ
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        width: u32,
+        height: u32,
+        seed: Option<u32>,
+        randomizer: Option<String>,
+        rotation: Option<String>,
+        buffer_rows: Option<u32>,
+    ) -> Result<GameSession, JsValue> {
+        Ok(Self::try_new(width, height, seed, randomizer, rotation, buffer_rows)?)
+    }
+
+    // seed省略時はランダムなシードを採番する（get_seedで後から取得して再現可能）
+    // randomizer: "7bag" | "14bag" | "tgm" | "random"（省略時は7bag）
+    // rotation: "srs" | "ars" | "nrs"（省略時はsrs）
+    // height: 可視段数。buffer_rows: その上の非表示の段数（省略時は20）
+    // ミノの座標はバッファを含めたフィールド全体の座標（可視部分の行 = y - bufferRows）
+    pub fn init_field(
+        &mut self,
+        width: u32,
+        height: u32,
+        seed: Option<u32>,
+        randomizer: Option<String>,
+        rotation: Option<String>,
+        buffer_rows: Option<u32>,
+    ) -> Result<JsValue, JsValue> {
+        self.reset(width, height, seed, randomizer, rotation, buffer_rows)?;
+        self.get_field()
+    }
+
+    pub fn get_rotation_system(&self) -> String {
+        self.rotation.name().to_string()
+    }
+
+    // スピン判定の方式: "corner" | "immobile" | "immobile-mini"（未知の名前はエラー）
+    pub fn set_spin_rule(&mut self, rule: &str) -> Result<(), JsValue> {
+        Ok(self.try_set_spin_rule(rule)?)
+    }
+
+    pub fn get_spin_rule(&self) -> String {
+        self.spin_rule.name().to_string()
+    }
+
+    pub fn is_game_over(&self) -> bool {
+        self.game_over.is_some()
+    }
+
+    // 固定すると次のミノは自動で出るので、固定直後に呼んだ場合は出ているミノをそのまま返す
+    pub fn spawn_tetromino(&mut self) -> Result<JsValue, JsValue> {
+        self.ensure_playing()?;
+        self.last_tspin = TSpinType::None;
+        let tetromino = self.spawn_next_piece();
+        to_js(&tetromino)
+    }
+
+    pub fn hold_tetromino(&mut self) -> Result<JsValue, JsValue> {
+        self.ensure_playing()?;
+        to_js(&self.hold_current())
+    }
+
+    // ホールド1回のコストをポイント台帳から支払わせる（省略時は15、フィーバー中は無料、0でコストなし）
+    // 支払いはエンジン内で完結するので、ホールド中にJSのコールバックを呼ばない
+    pub fn set_hold_cost(&mut self, cost: Option<u32>) {
+        self.hold_point_cost = cost.unwrap_or(HOLD_COST);
+    }
+
+    pub fn exchange_piece(&mut self) -> Result<JsValue, JsValue> {
+        self.ensure_playing()?;
+        to_js(&self.exchange_active())
+    }
+
+    pub fn emergency_delete_line(&mut self) -> Result<JsValue, JsValue> {
+        self.ensure_playing()?;
+        to_js(&self.emergency_delete_active())
+    }
+
+    // 実績解除の報酬ポイントを加算する
+    pub fn add_achievement_points(&mut self, amount: u32) -> Result<JsValue, JsValue> {
+        let transaction = self.points.earn(PointsSource::Achievement, amount);
+        to_js(&transaction)
+    }
+
+    pub fn get_points(&self) -> u32 {
+        self.points.balance()
+    }
+
+    // 次のエクスチェンジのコスト（フィーバー中は0）
+    pub fn get_exchange_cost(&self) -> u32 {
+        if self.fever.is_active() {
+            return 0;
+        }
+        self.points.exchange_cost()
+    }
+
+    // このゲームのポイント取引履歴
+    pub fn get_points_history(&self) -> Result<JsValue, JsValue> {
+        to_js(self.points.history())
+    }
+
+    // dir: "left" | "right" | "down"（それ以外はエラー）
+    pub fn move_tetromino(&mut self, dir: &str) -> Result<JsValue, JsValue> {
+        to_js(&self.try_move(dir)?)
+    }
+
+    // 経過時間(ms)だけエンジンの時間を進める（フィーバー・自然落下・固定猶予）
+    pub fn tick(&mut self, dt_ms: u32) -> Result<JsValue, JsValue> {
+        to_js(&self.tick_active(dt_ms))
+    }
+
+    // 開始レベルを設定（次のinit_fieldから適用、現在のゲームにも即時反映）
+    pub fn set_level(&mut self, level: u32) {
+        let lines = self.gravity.lines();
+        self.gravity = Gravity::new(level);
+        self.gravity.add_lines(lines);
+    }
+
+    pub fn get_level(&self) -> u32 {
+        self.gravity.level()
+    }
+
+    pub fn get_lines_cleared(&self) -> u32 {
+        self.gravity.lines()
+    }
+
+    // 固定猶予の設定（ガイドライン標準は500ms・15回）
+    pub fn set_lock_delay(&mut self, delay_ms: u32, max_resets: u32) {
+        self.lock_delay.configure(delay_ms, max_resets);
+    }
+
+    // dir: "right"（時計回り） | "left"（反時計回り） | "180"（それ以外はエラー）
+    pub fn rotate_tetromino(&mut self, dir: &str) -> Result<JsValue, JsValue> {
+        to_js(&self.try_rotate(dir)?)
+    }
+
+    // ゴースト（着地予測）の位置。hard_dropと同じ計算なので必ず一致する
+    pub fn get_ghost_position(&self) -> Result<JsValue, JsValue> {
+        to_js(&self.ghost_tetromino())
+    }
+
+    pub fn hard_drop(&mut self) -> Result<JsValue, JsValue> {
+        self.ensure_playing()?;
+        to_js(&self.hard_drop_active())
+    }
+
+    // NEXTに表示するミノ（先頭が次に出るミノ）
+    pub fn get_next_queue(&self) -> Result<JsValue, JsValue> {
+        to_js(&self.next_queue_kinds())
+    }
+
+    // NEXTの表示数を設定（1〜7に丸める）
+    pub fn set_next_queue_size(&mut self, size: u32) {
+        self.next_queue.set_preview_len(size as usize);
+    }
+
+    // ゲームオーバーの理由（プレイ中はnull）: "BlockOut" | "LockOut"
+    pub fn get_game_over_reason(&self) -> Result<JsValue, JsValue> {
+        to_js(&self.game_over)
+    }
+
+    pub fn get_seed(&self) -> u32 {
+        self.seed
+    }
+
+    pub fn get_score(&self) -> u32 {
+        self.scoring.score()
+    }
+
+    pub fn get_game_stats(&self) -> Result<JsValue, JsValue> {
+        to_js(&self.game_stats())
+    }
+
+    // 直前の固定で得たスコアの内訳（まだ固定していなければnull）
+    pub fn get_last_score_award(&self) -> Result<JsValue, JsValue> {
+        to_js(&self.scoring.last_award())
+    }
+
+    pub fn get_rank(&self) -> u8 {
+        self.dan_rank.current() as u8
+    }
+
+    pub fn get_fever_state(&self) -> Result<JsValue, JsValue> {
+        to_js(&self.fever.state())
+    }
+
+    // 段位の名前・色・閾値・次の段位までの進捗
+    pub fn get_rank_info(&self) -> Result<JsValue, JsValue> {
+        to_js(&RankInfo::for_score(self.get_score()))
+    }
+
+    // 可視部分のフィールド
+    pub fn get_field(&self) -> Result<JsValue, JsValue> {
+        to_js(&self.visible_field())
+    }
+
+    // バッファを含めたフィールド全体
+    pub fn get_full_field(&self) -> Result<JsValue, JsValue> {
+        to_js(&self.field.to_field())
+    }
+
+    pub fn get_buffer_rows(&self) -> u32 {
+        self.buffer_rows
+    }
+
+    pub fn get_current_tetromino(&self) -> Result<JsValue, JsValue> {
+        to_js(&self.tetromino)
+    }
+
+    pub fn get_last_tspin_type(&self) -> Result<JsValue, JsValue> {
+        to_js(&self.last_tspin)
+    }
+
+    pub fn get_game_state(&self) -> Result<JsValue, JsValue> {
+        let field = self.get_field()?;
+        let buffer_rows = JsValue::from(self.buffer_rows);
+        let tetromino = self.get_current_tetromino()?;
+        let last_tspin = self.get_last_tspin_type()?;
+        let last_wallkick = JsValue::from(self.last_wallkick);
+        let last_kick_index = JsValue::from(self.last_kick_index);
+        let next_queue = self.get_next_queue()?;
+        let hold_piece = to_js(&self.hold.piece())?;
+        let can_hold = JsValue::from(self.hold.can_hold());
+        let hold_cost = JsValue::from(self.hold_cost());
+        let lock_delay_remaining = JsValue::from(self.lock_delay.remaining_ms());
+        let lock_resets = JsValue::from(self.lock_delay.resets());
+        let level = JsValue::from(self.get_level());
+        let lines_cleared = JsValue::from(self.get_lines_cleared());
+        let game_over = JsValue::from(self.is_game_over());
+        let score = JsValue::from(self.get_score());
+        let last_award = self.get_last_score_award()?;
+        let rank = self.get_rank_info()?;
+        let fever = self.get_fever_state()?;
+        let points = JsValue::from(self.get_points());
+        let ghost_y = JsValue::from(self.ghost_tetromino().y);
+        let exchange_cost = JsValue::from(self.get_exchange_cost());
+        let combo_state = self.scoring.combo();
+        let combo = JsValue::from(combo_state.combo());
+        let b2b = JsValue::from(combo_state.b2b());
+        let max_combo = JsValue::from(combo_state.max_combo());
+        let game_over_reason = self.get_game_over_reason()?;
+
+        // JsValueを直接組み立て
+        let obj = js_sys::Object::new();
+        js_sys::Reflect::set(&obj, &"field".into(), &field)?;
+        js_sys::Reflect::set(&obj, &"bufferRows".into(), &buffer_rows)?;
+        js_sys::Reflect::set(&obj, &"tetromino".into(), &tetromino)?;
+        js_sys::Reflect::set(&obj, &"lastTspin".into(), &last_tspin)?;
+        js_sys::Reflect::set(&obj, &"lastWallkick".into(), &last_wallkick)?;
+        js_sys::Reflect::set(&obj, &"lastKickIndex".into(), &last_kick_index)?;
+        js_sys::Reflect::set(&obj, &"nextQueue".into(), &next_queue)?;
+        js_sys::Reflect::set(&obj, &"holdPiece".into(), &hold_piece)?;
+        js_sys::Reflect::set(&obj, &"canHold".into(), &can_hold)?;
+        js_sys::Reflect::set(&obj, &"holdCost".into(), &hold_cost)?;
+        js_sys::Reflect::set(&obj, &"lockDelayRemaining".into(), &lock_delay_remaining)?;
+        js_sys::Reflect::set(&obj, &"lockResets".into(), &lock_resets)?;
+        js_sys::Reflect::set(&obj, &"level".into(), &level)?;
+        js_sys::Reflect::set(&obj, &"linesCleared".into(), &lines_cleared)?;
+        js_sys::Reflect::set(&obj, &"gameOver".into(), &game_over)?;
+        js_sys::Reflect::set(&obj, &"score".into(), &score)?;
+        js_sys::Reflect::set(&obj, &"lastAward".into(), &last_award)?;
+        js_sys::Reflect::set(&obj, &"rank".into(), &rank)?;
+        js_sys::Reflect::set(&obj, &"fever".into(), &fever)?;
+        js_sys::Reflect::set(&obj, &"points".into(), &points)?;
+        js_sys::Reflect::set(&obj, &"ghostY".into(), &ghost_y)?;
+        js_sys::Reflect::set(&obj, &"exchangeCost".into(), &exchange_cost)?;
+        js_sys::Reflect::set(&obj, &"combo".into(), &combo)?;
+        js_sys::Reflect::set(&obj, &"b2b".into(), &b2b)?;
+        js_sys::Reflect::set(&obj, &"maxCombo".into(), &max_combo)?;
+        js_sys::Reflect::set(&obj, &"gameOverReason".into(), &game_over_reason)?;
+
+        Ok(obj.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fever;
+    use crate::get_tetromino_blocks;
+    use crate::scoring::ClearType;
+
+    // 可視10x20、バッファなし
+    fn board() -> GameSession {
+        GameSession::try_new(10, 20, Some(1), None, None, Some(0)).unwrap()
+    }
+
+    // 可視10x20＋バッファ20段
+    fn board_with_buffer() -> GameSession {
+        GameSession::try_new(10, 20, Some(1), None, None, None).unwrap()
+    }
+
+    fn filled_count(field: &BitBoard) -> usize {
+        field.to_field().cells.iter().filter(|&&c| c != 0).count()
+    }
+
+    #[test]
+    fn test_sessions_are_independent() {
+        let mut a = board();
+        let mut b = board();
+        a.spawn_piece(TetrominoKind::T);
+        b.spawn_piece(TetrominoKind::T);
+        a.move_active(MoveDirection::Left);
+        a.hard_drop_active();
+        assert_eq!(filled_count(&a.field), 4);
+        assert_eq!(filled_count(&b.field), 0);
+        assert_eq!(b.tetromino.kind, TetrominoKind::T);
+        assert!(a.get_score() > 0);
+        assert_eq!(b.get_score(), 0);
+    }
+
+    #[test]
+    fn test_seeded_spawn_order_is_reproducible() {
+        let mut a = GameSession::try_new(10, 20, Some(2024), None, None, None).unwrap();
+        let mut b = GameSession::try_new(10, 20, Some(2024), None, None, None).unwrap();
+        let first: Vec<TetrominoKind> = (0..21).map(|_| a.next_tetromino_kind()).collect();
+        let second: Vec<TetrominoKind> = (0..21).map(|_| b.next_tetromino_kind()).collect();
+        assert_eq!(first, second);
+        assert_eq!(a.get_seed(), 2024);
+    }
+
+    #[test]
+    fn test_spawn_follows_next_queue() {
+        let mut session = GameSession::try_new(10, 20, Some(7), None, None, None).unwrap();
+        let preview = session.next_queue_kinds();
+        assert_eq!(session.next_tetromino_kind(), preview[0]);
+        assert_eq!(session.next_queue_kinds()[0], preview[1]);
+    }
+
+    #[test]
+    fn test_hold_swaps_and_locks_until_fix() {
+        let mut session = GameSession::try_new(10, 20, Some(11), None, None, None).unwrap();
+        let kind = session.next_tetromino_kind();
+        let first = session.spawn_piece(kind);
+        let next = session.next_queue_kinds()[0];
+
+        let result = session.hold_current();
+        assert!(result.success);
+        assert_eq!(result.hold_piece, Some(first.kind));
+        assert_eq!(result.tetromino.kind, next);
+
+        let again = session.hold_current();
+        assert!(!again.success);
+        assert_eq!(again.reason, Some(HoldRejection::AlreadyUsed));
+        assert_eq!(again.tetromino.kind, next);
+
+        // 固定後は再びホールドでき、ホールド中のミノと入れ替わる
+        session.hard_drop_active();
+        let swapped = session.hold_current();
+        assert!(swapped.success);
+        assert_eq!(swapped.tetromino.kind, first.kind);
+    }
+
+    #[test]
+    fn test_down_collision_waits_for_lock_delay() {
+        let mut session = board();
+        session.spawn_piece(TetrominoKind::O);
+        let mut result = session.move_active(MoveDirection::Down);
+        while result.moved {
+            result = session.move_active(MoveDirection::Down);
+        }
+        assert!(!result.locked);
+        assert!(!session.advance_lock_delay(499).locked);
+        let locked = session.advance_lock_delay(1);
+        assert!(locked.locked);
+        assert_eq!(filled_count(&session.field), 4);
+    }
+
+    #[test]
+    fn test_move_reset_limit_forces_lock() {
+        let mut session = board();
+        session.spawn_piece(TetrominoKind::O);
+        while session.move_active(MoveDirection::Down).moved {}
+        let mut locked = false;
+        for i in 0..20 {
+            session.advance_lock_delay(100);
+            let dir = if i % 2 == 0 { MoveDirection::Left } else { MoveDirection::Right };
+            if session.move_active(dir).locked {
+                locked = true;
+                break;
+            }
+        }
+        assert!(locked);
+    }
+
+    #[test]
+    fn test_spawn_after_lock_keeps_bag_order() {
+        let mut expected = board();
+        let order: Vec<_> = (0..4).map(|_| expected.next_tetromino_kind()).collect();
+
+        // 従来どおり固定のたびにspawnしてもNEXTを読み飛ばさない
+        let mut session = board();
+        let mut kinds = vec![session.spawn_next_piece().kind];
+        for _ in 0..3 {
+            let locked = session.hard_drop_active();
+            let spawned = session.spawn_next_piece();
+            assert_eq!(spawned.kind, locked.tetromino.kind);
+            kinds.push(spawned.kind);
+        }
+        assert_eq!(kinds, order);
+
+        // 固定直後以外のspawnは次のミノを出す
+        assert_eq!(session.spawn_next_piece().kind, expected.next_tetromino_kind());
+    }
+
+    #[test]
+    fn test_hard_drop_locks_immediately() {
+        let mut session = board();
+        session.spawn_piece(TetrominoKind::I);
+        let result = session.hard_drop_active();
+        assert!(result.locked);
+        assert!(result.moved);
+        assert!((0..10).filter(|&x| session.field.cell(x, 19) != 0).count() == 4);
+    }
+
+    #[test]
+    fn test_tick_applies_gravity() {
+        let mut session = board();
+        session.set_level(1);
+        session.spawn_piece(TetrominoKind::T);
+        assert!(!session.tick_active(500).moved);
+        let result = session.tick_active(500);
+        assert!(result.moved);
+        assert_eq!(result.tetromino.y, 1);
+
+        // 20Gでは1フレームで底まで落ちる
+        session.set_level(20);
+        let result = session.tick_active(17);
+        assert_eq!(result.tetromino.y, 18);
+        assert!(!result.locked);
+    }
+
+    #[test]
+    fn test_block_out_ends_game_and_rejects_input() {
+        let mut session = board();
+        // 出現位置(4,0)付近を埋める
+        for x in 3..8 {
+            session.field.set(x, 0, 1);
+            session.field.set(x, 1, 1);
+        }
+        session.spawn_piece(TetrominoKind::T);
+        assert!(session.is_game_over());
+        assert_eq!(session.game_over, Some(GameOverReason::BlockOut));
+
+        let before = session.tetromino.clone();
+        let result = session.move_active(MoveDirection::Left);
+        assert!(!result.moved);
+        assert!(result.game_over);
+        assert_eq!(result.tetromino.x, before.x);
+        assert!(!session.hard_drop_active().locked);
+        assert_eq!(session.hold_current().reason, Some(HoldRejection::GameOver));
+    }
+
+    #[test]
+    fn test_invalid_direction_and_input_after_game_over_are_errors() {
+        let mut session = board();
+        session.spawn_piece(TetrominoKind::T);
+        let x = session.tetromino.x;
+        assert_eq!(session.try_move("up").err(), Some(EngineError::InvalidDirection("up".to_string())));
+        assert_eq!(session.try_rotate("").err(), Some(EngineError::InvalidDirection(String::new())));
+        assert_eq!(session.tetromino.x, x);
+        assert!(session.try_move("left").unwrap().moved);
+        assert_eq!(session.try_rotate("180").unwrap().tetromino.rotation, 2);
+
+        session.game_over = Some(GameOverReason::BlockOut);
+        assert_eq!(session.try_move("left").err(), Some(EngineError::GameOver));
+        assert_eq!(session.try_rotate("right").err(), Some(EngineError::GameOver));
+        assert_eq!(session.ensure_playing(), Err(EngineError::GameOver));
+    }
+
+    #[test]
+    fn test_lock_awards_line_clear_and_drop_score() {
+        let mut session = board();
+        // 最下段を左4マス以外埋めておき、横向きIで1ライン消去
+        for x in 4..10 {
+            session.field.set(x, 19, 1);
+        }
+        session.spawn_piece(TetrominoKind::I);
+        session.tetromino.x = 0;
+        session.move_active(MoveDirection::Down);
+        session.move_active(MoveDirection::Down);
+        let result = session.hard_drop_active();
+        let award = result.award.expect("award on lock");
+        assert_eq!(award.lines, 1);
+        assert_eq!(award.line_clear, 100);
+        assert_eq!(award.placement, 10);
+        assert_eq!(award.soft_drop, 1);
+        // Iミノは0行目に出現し、ソフトドロップ2段の後に19行目まで17段落ちる
+        assert_eq!(award.hard_drop, 17);
+        assert_eq!(session.get_score(), award.total);
+        assert!(session.field.is_empty());
+        // 盤面が空になったので全消し
+        assert_eq!(award.clear_type, ClearType::PerfectClear);
+        assert_eq!(award.perfect_clear_bonus, 800);
+        assert_eq!(session.game_stats().perfect_clear_count, 1);
+        // 900点以上なので二段へ昇格（初段を飛ばして2段分のボーナス）
+        let promotion = result.promotion.expect("promotion");
+        assert_eq!(promotion.to, 2);
+        assert_eq!(promotion.bonus_points, 100);
+        assert_eq!(session.game_stats().dan_rank, 2);
+    }
+
+    #[test]
+    fn test_game_stats_track_max_combo() {
+        let mut session = board();
+        // 2行とも左4マス以外を埋め、横向きIで1ラインずつ消して2連続消去
+        for y in 18..20 {
+            for x in 4..10 {
+                session.field.set(x, y, 1);
+            }
+        }
+        for _ in 0..2 {
+            session.spawn_piece(TetrominoKind::I);
+            session.tetromino.x = 0;
+            session.hard_drop_active();
+        }
+        let stats = session.game_stats();
+        assert_eq!(stats.max_combo, 1);
+        assert_eq!(stats.lines_cleared, 2);
+        assert_eq!(stats.blocks_placed, 2);
+        assert_eq!(session.scoring.combo().combo(), 1);
+    }
+
+    #[test]
+    fn test_game_stats_track_games_and_play_time() {
+        let mut session = board();
+        assert_eq!(session.game_stats().games_played, 1);
+        assert_eq!(session.game_stats().play_time, 0);
+
+        session.spawn_piece(TetrominoKind::T);
+        session.tick_active(1500);
+        // レベル1のupdateは1回1秒
+        session.gravity_step();
+        assert_eq!(session.game_stats().play_time, 2);
+
+        // 次のゲームでは遊んだ回数が増え、時間は0から数え直す
+        session.reset(10, 20, Some(1), None, None, Some(0)).unwrap();
+        let stats = session.game_stats();
+        assert_eq!(stats.games_played, 2);
+        assert_eq!(stats.play_time, 0);
+    }
+
+    #[test]
+    fn test_fever_triggers_from_placements_and_multiplies_score() {
+        let mut session = board();
+        session.set_hold_cost(None);
+        let mut started = None;
+        for i in 0..fever::FEVER_BLOCKS_NEEDED {
+            session.spawn_piece(TetrominoKind::O);
+            // 積み上がらないよう毎回盤面を空にする
+            session.field = BitBoard::new(10, 20);
+            let result = session.hard_drop_active();
+            if result.fever.is_some() {
+                started = Some(i);
+            }
+        }
+        assert_eq!(started, Some(fever::FEVER_BLOCKS_NEEDED - 1));
+        assert_eq!(session.game_stats().fever_count, 1);
+        // フィーバー中のホールドは無料
+        assert_eq!(session.hold_cost(), 0);
+
+        session.field = BitBoard::new(10, 20);
+        let award = session.hard_drop_active().award.unwrap();
+        assert_eq!(award.multiplier, fever::FEVER_SCORE_MULTIPLIER);
+
+        let ended = session.tick_active(fever::FEVER_DURATION_MS);
+        assert_eq!(ended.fever, Some(FeverEvent::Ended));
+        assert_eq!(session.hold_cost(), HOLD_COST);
+    }
+
+    #[test]
+    fn test_exchange_spends_points_and_swaps_with_next() {
+        let mut session = GameSession::try_new(10, 20, Some(3), None, None, Some(0)).unwrap();
+        session.spawn_piece(TetrominoKind::T);
+        let failed = session.exchange_active();
+        assert!(!failed.success);
+        assert_eq!(failed.error, Some(InsufficientPoints { cost: 45, balance: 0 }));
+
+        session.points.earn(PointsSource::Achievement, 100);
+        let next = session.next_queue_kinds()[0];
+        let result = session.exchange_active();
+        assert!(result.success);
+        assert_eq!(result.tetromino.kind, next);
+        assert_eq!(result.transaction.unwrap().amount, -45);
+        assert_eq!(session.get_points(), 55);
+        assert_eq!(session.get_exchange_cost(), 65);
+
+        // 固定で配置ポイントが入り、累積コストがリセットされる
+        let locked = session.hard_drop_active();
+        assert_eq!(locked.points[0].source, PointsSource::Placement);
+        assert_eq!(session.get_points(), 65);
+        assert_eq!(session.get_exchange_cost(), 45);
+    }
+
+    #[test]
+    fn test_emergency_delete_removes_bottom_row() {
+        let mut session = board();
+        session.spawn_piece(TetrominoKind::T);
+        for x in 0..10 {
+            session.field.set(x, 19, 1);
+        }
+        session.field.set(3, 18, 1);
+        assert!(!session.emergency_delete_active().success);
+
+        session.points.earn(PointsSource::Achievement, LINE_DELETE_COST);
+        let result = session.emergency_delete_active();
+        assert!(result.success);
+        assert_eq!(session.get_points(), 0);
+        assert_eq!(filled_count(&session.field), 1);
+        assert_eq!(session.field.cell(3, 19), 1);
+    }
+
+    #[test]
+    fn test_hold_point_cost_uses_ledger() {
+        let mut session = board();
+        session.set_hold_cost(None);
+        assert_eq!(session.hold_cost(), HOLD_COST);
+        session.spawn_piece(TetrominoKind::T);
+        let result = session.hold_current();
+        assert_eq!(result.reason, Some(HoldRejection::CostNotPaid));
+
+        session.points.earn(PointsSource::Achievement, 20);
+        let result = session.hold_current();
+        assert!(result.success);
+        assert_eq!(result.transaction.unwrap().amount, -(HOLD_COST as i64));
+        assert_eq!(session.get_points(), 5);
+
+        // 使用済みのホールドでは支払わない
+        assert_eq!(session.hold_current().reason, Some(HoldRejection::AlreadyUsed));
+        assert_eq!(session.get_points(), 5);
+
+        session.set_hold_cost(Some(0));
+        assert_eq!(session.hold_cost(), 0);
+
+        // 任意のコストも台帳から支払う
+        session.set_hold_cost(Some(5));
+        session.hard_drop_active();
+        let result = session.hold_current();
+        assert!(result.success);
+        assert_eq!(result.transaction.unwrap().amount, -5);
+    }
+
+    #[test]
+    fn test_ghost_matches_hard_drop() {
+        let mut session = board();
+        session.field.set(5, 15, 1);
+        session.field.set(4, 19, 1);
+        session.spawn_piece(TetrominoKind::T);
+        session.tetromino.x = 4;
+        let ghost = session.ghost_tetromino();
+        assert_eq!(ghost.y, 13);
+        session.hard_drop_active();
+        // T(0度)の中心は(x+1, y+1)
+        assert_eq!(cell::cell_kind(session.field.cell(5, 14)), Some("T"));
+        assert_eq!(cell::cell_kind(session.field.cell(5, 13)), Some("T"));
+    }
+
+    #[test]
+    fn test_rotation_system_selected_per_session() {
+        // クラシックではTミノが平らな面を上にして出現する
+        let nrs = Some("nrs".to_string());
+        let mut session = GameSession::try_new(10, 20, Some(1), None, nrs, Some(0)).unwrap();
+        assert_eq!(session.get_rotation_system(), "nrs");
+        let spawned = session.spawn_piece(TetrominoKind::T);
+        assert_eq!(spawned.rotation, 2);
+        assert_eq!(board().spawn_piece(TetrominoKind::T).rotation, 0);
+    }
+
+    #[test]
+    fn test_width_outside_row_bits_is_rejected() {
+        for width in [0, MAX_WIDTH + 1] {
+            let result = GameSession::try_new(width, 20, Some(1), None, None, None);
+            assert!(matches!(result, Err(EngineError::InvalidInput(_))), "width {}", width);
+        }
+        let session = GameSession::try_new(MAX_WIDTH, 20, Some(1), None, None, None).unwrap();
+        assert_eq!(session.field.width(), MAX_WIDTH);
+    }
+
+    #[test]
+    fn test_unknown_names_are_rejected() {
+        let names = [(Some("7BAG"), None), (None, Some("ARS")), (None, Some("tgm "))];
+        for (randomizer, rotation) in names {
+            let result = GameSession::try_new(
+                10,
+                20,
+                Some(1),
+                randomizer.map(str::to_string),
+                rotation.map(str::to_string),
+                None,
+            );
+            assert!(matches!(result, Err(EngineError::InvalidInput(_))));
+        }
+
+        // 不正な指定のreset/set_spin_ruleは現在のゲームを変更しない
+        let mut session =
+            GameSession::try_new(10, 20, Some(1), None, Some("ars".to_string()), None).unwrap();
+        let err = session.reset(10, 20, Some(1), None, Some("SRS".to_string()), None);
+        assert!(matches!(err, Err(EngineError::InvalidInput(_))));
+        assert_eq!(session.get_rotation_system(), "ars");
+
+        session.try_set_spin_rule("immobile").unwrap();
+        let err = session.try_set_spin_rule("Corner");
+        assert!(matches!(err, Err(EngineError::InvalidInput(_))));
+        assert_eq!(session.get_spin_rule(), "immobile");
+    }
+
+    #[test]
+    fn test_moving_after_rotation_clears_rotated_flag() {
+        let mut session = board();
+        session.spawn_piece(TetrominoKind::T);
+        assert!(session.last_rotation.is_none());
+        session.rotate_active(RotationDirection::Clockwise);
+        assert_eq!(session.last_rotation, Some(RotationDirection::Clockwise));
+        session.move_active(MoveDirection::Left);
+        assert!(session.last_rotation.is_none());
+        assert_eq!(SpinRule::parse("allspin"), Some(SpinRule::Immobile));
+    }
+
+    #[test]
+    fn test_t_spin_requires_rotation_as_last_action() {
+        // 下向きのTが入る穴（上の2角と左下の角が埋まっている）
+        let slot = |session: &mut GameSession| {
+            for x in (0..10).filter(|&x| x != 4 && x != 5) {
+                session.field.set(x, 19, 1);
+            }
+            session.field.set(3, 17, 1);
+            session.field.set(5, 17, 1);
+        };
+
+        // 回転で入れればT-Spin Mini
+        let mut session = board();
+        slot(&mut session);
+        session.spawn_piece(TetrominoKind::T);
+        session.tetromino = Tetromino { kind: TetrominoKind::T, x: 3, y: 17, rotation: 1 };
+        assert_eq!(session.rotate_active(RotationDirection::Clockwise).kick_index, 0);
+        session.hard_drop_active();
+        assert_eq!(session.last_tspin, TSpinType::Mini);
+
+        // 回転した後に横移動で入れた場合はT-Spinなし
+        let mut session = board();
+        slot(&mut session);
+        session.spawn_piece(TetrominoKind::T);
+        session.rotate_active(RotationDirection::Half);
+        session.tetromino = Tetromino { kind: TetrominoKind::T, x: 4, y: 17, rotation: 2 };
+        assert!(session.move_active(MoveDirection::Left).moved);
+        session.hard_drop_active();
+        assert_eq!(session.last_tspin, TSpinType::None);
+    }
+
+    #[test]
+    fn test_failed_rotation_keeps_previous_kick() {
+        let mut session = board();
+        session.spawn_piece(TetrominoKind::T);
+        // 右向きのT(3,14)が5番目のキック(+1,+2)で上向きになって入る穴。それ以外は埋める
+        // 前側の角は1つだけなので、TSTキックでなければMini
+        let open = [(4, 14), (4, 15), (5, 15), (4, 16), (5, 16), (4, 17), (5, 17), (6, 17)];
+        for y in 0..20 {
+            for x in 0..10 {
+                if !open.contains(&(x, y)) {
+                    session.field.set(x, y, 1);
+                }
+            }
+        }
+        session.tetromino = Tetromino { kind: TetrominoKind::T, x: 3, y: 14, rotation: 1 };
+        assert_eq!(session.rotate_active(RotationDirection::CounterClockwise).kick_index, 4);
+        // 穴の中ではもう回転できない
+        assert_eq!(session.rotate_active(RotationDirection::CounterClockwise).kick_index, -1);
+        session.hard_drop_active();
+        assert_eq!(session.last_tspin, TSpinType::Normal);
+    }
+
+    #[test]
+    fn test_locked_cells_keep_piece_kind_and_hold_flag() {
+        let mut session = board();
+        session.spawn_piece(TetrominoKind::T);
+        session.hard_drop_active();
+        let kinds: Vec<_> = session.field.to_field().cells.iter().filter_map(|&c| cell::cell_kind(c)).collect();
+        assert_eq!(kinds, vec!["T"; 4]);
+        assert!(session.field.to_field().cells.iter().all(|&c| !cell::has_flag(c, cell::FLAG_FROM_HOLD)));
+
+        // ホールドから出したミノにはフラグが付く
+        let mut session = board();
+        session.spawn_piece(TetrominoKind::I);
+        session.hold_current();
+        session.hold.on_lock();
+        session.spawn_piece(TetrominoKind::O);
+        let result = session.hold_current();
+        assert_eq!(result.tetromino.kind, TetrominoKind::I);
+        session.hard_drop_active();
+        let held: Vec<_> = session.field.to_field().cells.iter().filter(|&&c| c != 0).copied().collect();
+        assert_eq!(held.len(), 4);
+        assert!(held.iter().all(|&c| cell::cell_kind(c) == Some("I") && cell::has_flag(c, cell::FLAG_FROM_HOLD)));
+    }
+
+    #[test]
+    fn test_spawn_in_buffer_rows_above_visible_field() {
+        let mut session = board_with_buffer();
+        let t = session.spawn_piece(TetrominoKind::T);
+        // 可視領域の最上段は y=20。Tミノは21〜22段目（y=18, 19）に出現する
+        assert_eq!(t.y, 18);
+        let rows: Vec<i32> = get_tetromino_blocks(session.system(), TetrominoKind::T, t.x, t.y, t.rotation).iter().map(|&(_, y)| y).collect();
+        assert!(rows.iter().all(|&y| y == 18 || y == 19));
+        assert!(!session.is_game_over());
+        // ハードドロップで可視領域の底まで落ちる
+        session.hard_drop_active();
+        let visible = session.visible_field();
+        assert_eq!(visible.height, 20);
+        assert_eq!(visible.cells.len(), 200);
+        assert_eq!(visible.cells[19 * 10..].iter().filter(|&&c| c != 0).count(), 3);
+    }
+
+    #[test]
+    fn test_lock_out_uses_visible_top() {
+        // 可視領域のすぐ上で固定したらロックアウト
+        let mut session = board_with_buffer();
+        session.spawn_piece(TetrominoKind::I);
+        for x in 0..10 {
+            session.field.set(x, 20, 1);
+        }
+        let result = session.hard_drop_active();
+        assert!(result.game_over);
+        assert_eq!(session.game_over, Some(GameOverReason::LockOut));
+    }
+
+    #[test]
+    fn test_spawn_centered_on_non_default_widths() {
+        for width in [4u32, 7, 10, 12, 20] {
+            let mut session = GameSession::try_new(width, 20, Some(1), None, None, None).unwrap();
+            for kind in TetrominoKind::ALL {
+                let t = session.spawn_piece(kind);
+                assert!(!session.is_game_over(), "{} block out on width {}", kind, width);
+                let blocks = get_tetromino_blocks(session.system(), kind, t.x, t.y, t.rotation);
+                let min_x = blocks.iter().map(|&(x, _)| x).min().unwrap();
+                let max_x = blocks.iter().map(|&(x, _)| x).max().unwrap();
+                // 左右の余白の差は0か1（奇数余りは左寄り）
+                let left = min_x;
+                let right = width as i32 - 1 - max_x;
+                assert!(right - left == 0 || right - left == 1, "{} on width {}: {:?}", kind, width, blocks);
+                // 21〜22段目に収まる
+                assert!(blocks.iter().all(|&(_, y)| y == 18 || y == 19), "{} on width {}", kind, width);
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::EngineError;
+
+// ミノの種類
+// JSとの間では従来通り "I" 〜 "L" の文字列でやり取りし、未知の文字列は読み取り時にエラーにする
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String")]
+pub enum TetrominoKind {
+    I,
+    O,
+    T,
+    S,
+    Z,
+    J,
+    L,
+}
+
+impl TetrominoKind {
+    // 乱数器・マスの値で使う並び順
+    pub const ALL: [TetrominoKind; 7] = [
+        TetrominoKind::I,
+        TetrominoKind::O,
+        TetrominoKind::T,
+        TetrominoKind::S,
+        TetrominoKind::Z,
+        TetrominoKind::J,
+        TetrominoKind::L,
+    ];
+
+    pub fn parse(name: &str) -> Result<Self, EngineError> {
+        match name {
+            "I" => Ok(TetrominoKind::I),
+            "O" => Ok(TetrominoKind::O),
+            "T" => Ok(TetrominoKind::T),
+            "S" => Ok(TetrominoKind::S),
+            "Z" => Ok(TetrominoKind::Z),
+            "J" => Ok(TetrominoKind::J),
+            "L" => Ok(TetrominoKind::L),
+            _ => Err(EngineError::UnknownPieceKind(name.to_string())),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TetrominoKind::I => "I",
+            TetrominoKind::O => "O",
+            TetrominoKind::T => "T",
+            TetrominoKind::S => "S",
+            TetrominoKind::Z => "Z",
+            TetrominoKind::J => "J",
+            TetrominoKind::L => "L",
+        }
+    }
+
+    // ALL内の位置（形状テーブルの添字）
+    pub(crate) fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl TryFrom<String> for TetrominoKind {
+    type Error = EngineError;
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        Self::parse(&name)
+    }
+}
+
+impl fmt::Display for TetrominoKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kind_names_round_trip() {
+        for (i, kind) in TetrominoKind::ALL.into_iter().enumerate() {
+            assert_eq!(kind.index(), i);
+            assert_eq!(TetrominoKind::parse(kind.name()), Ok(kind));
+        }
+        assert_eq!(TetrominoKind::parse("X"), Err(EngineError::UnknownPieceKind("X".to_string())));
+        assert!(TetrominoKind::parse("t").is_err());
+    }
+
+    #[test]
+    fn test_serde_uses_piece_letters() {
+        assert_eq!(serde_json::to_string(&TetrominoKind::T).unwrap(), "\"T\"");
+        assert_eq!(serde_json::from_str::<TetrominoKind>("\"I\"").unwrap(), TetrominoKind::I);
+        assert!(serde_json::from_str::<TetrominoKind>("\"X\"").is_err());
+        assert!(serde_json::from_str::<TetrominoKind>("\"\"").is_err());
+    }
+}